@@ -1,7 +1,7 @@
 use sp_core::{Pair, Public, sr25519};
 use node_template_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	NftConfig, SudoConfig, SystemConfig, WASM_BINARY, Signature
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
@@ -38,6 +38,17 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	)
 }
 
+/// The Zodiac sample collection: a domain owned by `owner` with a token per sign, one of each
+/// held by every account in `holders`.
+pub fn zodiac_genesis(owner: AccountId, holders: Vec<AccountId>) -> NftConfig {
+	const SIGNS: u64 = 12;
+	NftConfig {
+		domains: vec![(owner.clone(), b"ZOD".to_vec(), b"Zodiac".to_vec())],
+		tokens: (0..SIGNS).map(|_| (0, owner.clone(), 100, b"https://barkingmad.io/assets".to_vec())).collect(),
+		balances: holders.iter().flat_map(|holder| (1..=SIGNS).map(move |token_id| (holder.clone(), 0, token_id, 1))).collect(),
+	}
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
 
@@ -62,6 +73,11 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 			],
+			// Sample NFTs
+			zodiac_genesis(
+				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				vec![get_account_id_from_seed::<sr25519::Public>("Bob")],
+			),
 			true,
 		),
 		// Bootnodes
@@ -110,6 +126,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 			],
+			// Sample NFTs
+			Default::default(),
 			true,
 		),
 		// Bootnodes
@@ -131,6 +149,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	nft_config: NftConfig,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -153,5 +172,6 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			key: root_key,
 		}),
+		pallet_nft: Some(nft_config),
 	}
 }
@@ -0,0 +1,45 @@
+//! Runtime API definition for the nft pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// decl_runtime_apis! bounds the generics twice and adds the block and version arguments
+#![allow(clippy::multiple_bound_locations, clippy::too_many_arguments)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_nft::{Bid, BidId, Domain, Token};
+
+sp_api::decl_runtime_apis! {
+	pub trait NftApi<AccountId, DomainId, TokenId, Balance, Price> where
+		AccountId: Codec,
+		DomainId: Codec,
+		TokenId: Codec,
+		Balance: Codec,
+		Price: Codec,
+	{
+		/// The amount `spender` may still transfer out of `owner`'s balance of a token.
+		fn allowance(owner: AccountId, spender: AccountId, domain_id: DomainId, token_id: TokenId) -> Balance;
+		/// The bids waiting on the book for a token.
+		fn bids(domain_id: DomainId, token_id: TokenId) -> Vec<(BidId, Bid<AccountId, Balance, Price>)>;
+		/// The creator of a token and the royalty they are owed on a sale at `sale_price`.
+		fn royalty_info(domain_id: DomainId, token_id: TokenId, sale_price: Price) -> Option<(AccountId, Price)>;
+		/// The key/value attributes of a token, or of the domain when `token_id` is `None`.
+		fn attributes(domain_id: DomainId, token_id: Option<TokenId>) -> Vec<(Vec<u8>, Vec<u8>)>;
+		/// The resolved metadata URI of a token.
+		fn token_uri(domain_id: DomainId, token_id: TokenId) -> Option<Vec<u8>>;
+		/// A domain, if it exists.
+		fn domain(domain_id: DomainId) -> Option<Domain<TokenId, AccountId>>;
+		/// A token, if it exists.
+		fn token(domain_id: DomainId, token_id: TokenId) -> Option<Token<AccountId, Balance>>;
+		/// How much of a token `owner` holds.
+		fn balance_of(owner: AccountId, domain_id: DomainId, token_id: TokenId) -> Balance;
+		/// The balance for each `(owner, domain_id, token_id)`, in the same order.
+		fn balance_of_batch(queries: Vec<(AccountId, DomainId, TokenId)>) -> Vec<Balance>;
+		/// How much of a token is in circulation.
+		fn total_supply(domain_id: DomainId, token_id: TokenId) -> Balance;
+		/// A page of the tokens `owner` holds with their balances, and the cursor of the next page.
+		fn tokens_of(owner: AccountId, cursor: u32, limit: u32) -> (Vec<(DomainId, TokenId, Balance)>, Option<u32>);
+		/// A page of the holders of a token with their balances, and the cursor of the next page.
+		fn holders_of(domain_id: DomainId, token_id: TokenId, cursor: u32, limit: u32) -> (Vec<(AccountId, Balance)>, Option<u32>);
+	}
+}
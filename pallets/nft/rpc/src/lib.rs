@@ -0,0 +1,162 @@
+//! RPC interface for the nft pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_nft_rpc_runtime_api::{Domain, NftApi as NftRuntimeApi, Token};
+
+#[rpc]
+pub trait NftApi<BlockHash, AccountId, DomainId, TokenId, Balance> {
+	/// The uri as text, any bytes that aren't valid UTF-8 come back as U+FFFD
+	#[rpc(name = "nft_tokenUri")]
+	fn token_uri(&self, domain_id: DomainId, token_id: TokenId, at: Option<BlockHash>) -> Result<Option<String>>;
+
+	#[rpc(name = "nft_domain")]
+	fn domain(&self, domain_id: DomainId, at: Option<BlockHash>) -> Result<Option<Domain<TokenId, AccountId>>>;
+
+	#[rpc(name = "nft_token")]
+	fn token(&self, domain_id: DomainId, token_id: TokenId, at: Option<BlockHash>) -> Result<Option<Token<AccountId, Balance>>>;
+
+	#[rpc(name = "nft_balanceOf")]
+	fn balance_of(&self, owner: AccountId, domain_id: DomainId, token_id: TokenId, at: Option<BlockHash>) -> Result<Balance>;
+
+	#[rpc(name = "nft_balanceOfBatch")]
+	fn balance_of_batch(&self, queries: Vec<(AccountId, DomainId, TokenId)>, at: Option<BlockHash>) -> Result<Vec<Balance>>;
+
+	#[rpc(name = "nft_totalSupply")]
+	fn total_supply(&self, domain_id: DomainId, token_id: TokenId, at: Option<BlockHash>) -> Result<Balance>;
+
+	#[rpc(name = "nft_tokensOf")]
+	fn tokens_of(&self, owner: AccountId, cursor: Option<u32>, limit: u32, at: Option<BlockHash>) -> Result<Page<(DomainId, TokenId, Balance)>>;
+
+	#[rpc(name = "nft_holdersOf")]
+	fn holders_of(&self, domain_id: DomainId, token_id: TokenId, cursor: Option<u32>, limit: u32, at: Option<BlockHash>) -> Result<Page<(AccountId, Balance)>>;
+}
+
+/// One page of a listing. Pass `next_cursor` back to get the following page, it is `None` on the last one.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<Item> {
+	pub items: Vec<Item>,
+	pub next_cursor: Option<u32>,
+}
+
+impl<Item> From<(Vec<Item>, Option<u32>)> for Page<Item> {
+	fn from((items, next_cursor): (Vec<Item>, Option<u32>)) -> Self {
+		Page { items, next_cursor }
+	}
+}
+
+/// Error code for failures while calling into the runtime.
+const RUNTIME_ERROR: i64 = 1;
+
+/// Answers nft queries against the runtime API. `M` carries the runtime's block, account,
+/// balance and price types as `(Block, AccountId, Balance, Price)`.
+pub struct Nft<C, M> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> Nft<C, M> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Balance, Price> Nft<C, (Block, AccountId, Balance, Price)>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	/// The block to query, the best one unless the caller picked one.
+	fn at(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, DomainId, TokenId, Balance, Price> NftApi<<Block as BlockT>::Hash, AccountId, DomainId, TokenId, Balance>
+	for Nft<C, (Block, AccountId, Balance, Price)>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: NftRuntimeApi<Block, AccountId, DomainId, TokenId, Balance, Price>,
+	AccountId: Codec + Send + Sync + 'static,
+	DomainId: Codec,
+	TokenId: Codec,
+	Balance: Codec + Send + Sync + 'static,
+	Price: Codec + Send + Sync + 'static,
+{
+	fn token_uri(&self, domain_id: DomainId, token_id: TokenId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<String>> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		api.token_uri(&at, domain_id, token_id)
+			.map(|uri| uri.map(|uri| String::from_utf8_lossy(&uri).into_owned()))
+			.map_err(|e| runtime_error("Unable to query token uri.", e))
+	}
+
+	fn domain(&self, domain_id: DomainId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Domain<TokenId, AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		api.domain(&at, domain_id)
+			.map_err(|e| runtime_error("Unable to query domain.", e))
+	}
+
+	fn token(&self, domain_id: DomainId, token_id: TokenId, at: Option<<Block as BlockT>::Hash>) -> Result<Option<Token<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		api.token(&at, domain_id, token_id)
+			.map_err(|e| runtime_error("Unable to query token.", e))
+	}
+
+	fn balance_of(&self, owner: AccountId, domain_id: DomainId, token_id: TokenId, at: Option<<Block as BlockT>::Hash>) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		api.balance_of(&at, owner, domain_id, token_id)
+			.map_err(|e| runtime_error("Unable to query balance.", e))
+	}
+
+	fn balance_of_batch(&self, queries: Vec<(AccountId, DomainId, TokenId)>, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<Balance>> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		api.balance_of_batch(&at, queries)
+			.map_err(|e| runtime_error("Unable to query balances.", e))
+	}
+
+	fn total_supply(&self, domain_id: DomainId, token_id: TokenId, at: Option<<Block as BlockT>::Hash>) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		api.total_supply(&at, domain_id, token_id)
+			.map_err(|e| runtime_error("Unable to query total supply.", e))
+	}
+
+	fn tokens_of(&self, owner: AccountId, cursor: Option<u32>, limit: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Page<(DomainId, TokenId, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		api.tokens_of(&at, owner, cursor.unwrap_or_default(), limit)
+			.map(Into::into)
+			.map_err(|e| runtime_error("Unable to query tokens of account.", e))
+	}
+
+	fn holders_of(&self, domain_id: DomainId, token_id: TokenId, cursor: Option<u32>, limit: u32, at: Option<<Block as BlockT>::Hash>) -> Result<Page<(AccountId, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = self.at(at);
+		api.holders_of(&at, domain_id, token_id, cursor.unwrap_or_default(), limit)
+			.map(Into::into)
+			.map_err(|e| runtime_error("Unable to query holders of token.", e))
+	}
+}
@@ -0,0 +1,41 @@
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::Get,
+    IterableStorageDoubleMap, StorageDoubleMap,
+};
+use sp_std::vec::Vec;
+
+use crate::{Attributes, Error, Module, RawEvent, Tokens, Trait};
+
+impl<T: Trait> Module<T> {
+    pub(crate) fn do_set_attribute(domain_id: T::DomainId, token_id: Option<T::TokenId>, key: Vec<u8>, value: Vec<u8>) -> DispatchResult {
+        ensure!(key.len() as u32 <= T::KeyLimit::get(), Error::<T>::KeyTooLong);
+        ensure!(value.len() as u32 <= T::ValueLimit::get(), Error::<T>::ValueTooLong);
+        if let Some(token_id) = token_id {
+            ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+        }
+
+        let deposit = Self::deposit_for(T::AttributeDepositBase::get(), key.len() + value.len());
+        let old_deposit = Attributes::<T>::get((domain_id, token_id), &key).map(|(_, deposit)| deposit).unwrap_or_default();
+        Self::adjust_domain_deposit(domain_id, old_deposit, deposit)?;
+
+        Attributes::<T>::insert((domain_id, token_id), &key, (value.clone(), deposit));
+        Self::deposit_event(RawEvent::AttributeSet(domain_id, token_id, key, value));
+        Ok(())
+    }
+
+    pub(crate) fn do_clear_attribute(domain_id: T::DomainId, token_id: Option<T::TokenId>, key: Vec<u8>) -> DispatchResult {
+        let (_, deposit) = Attributes::<T>::take((domain_id, token_id), &key).ok_or(Error::<T>::AttributeNotFound)?;
+        Self::adjust_domain_deposit(domain_id, deposit, Default::default())?;
+        Self::deposit_event(RawEvent::AttributeCleared(domain_id, token_id, key));
+        Ok(())
+    }
+
+    /// All attributes of a token, or of the domain itself when `token_id` is `None`
+    pub fn attributes_of(domain_id: T::DomainId, token_id: Option<T::TokenId>) -> Vec<(Vec<u8>, Vec<u8>)> {
+        Attributes::<T>::iter_prefix((domain_id, token_id))
+            .map(|(key, (value, _))| (key, value))
+            .collect()
+    }
+}
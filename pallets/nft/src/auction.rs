@@ -0,0 +1,132 @@
+use frame_support::{
+    dispatch::{result::Result, DispatchError, DispatchResult},
+    ensure,
+    traits::{Get, ReservableCurrency},
+    weights::Weight,
+    StorageMap, StorageValue,
+};
+use sp_runtime::traits::{One, Saturating, Zero};
+
+use crate::{with_transaction_result, Auction, AuctionEndings, AuctionId, Auctions, BalanceOf, Error, Module, NextAuctionId, RawEvent, Trait, WeightInfo};
+
+impl<T: Trait> Module<T> {
+    pub(crate) fn do_create_auction(seller: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance, reserve_price: BalanceOf<T>, min_increment: BalanceOf<T>, end: T::BlockNumber) -> Result<AuctionId, DispatchError> {
+        ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToList);
+        ensure!(end > frame_system::Module::<T>::block_number(), Error::<T>::InvalidAuctionEnd);
+        ensure!((AuctionEndings::<T>::get(end).len() as u32) < T::MaxAuctionsEndingPerBlock::get(), Error::<T>::TooManyAuctionsEnding);
+        Self::ensure_transferable(&seller, &seller, domain_id, token_id)?;
+
+        let auction_id = NextAuctionId::try_mutate(|next_id| -> Result<AuctionId, DispatchError> {
+            let current_id = *next_id;
+            *next_id = next_id.checked_add(1).ok_or(Error::<T>::AuctionIdOverflow)?;
+            Ok(current_id)
+        })?;
+        Self::lock(&seller, domain_id, token_id, quantity)?;
+        Auctions::<T>::insert(auction_id, Auction {
+            seller: seller.clone(),
+            domain_id,
+            token_id,
+            quantity,
+            reserve_price,
+            min_increment,
+            end,
+            highest_bid: None,
+        });
+        AuctionEndings::<T>::mutate(end, |endings| endings.push(auction_id));
+        Self::deposit_event(RawEvent::AuctionCreated(auction_id, seller, domain_id, token_id, quantity, reserve_price, end));
+        Ok(auction_id)
+    }
+
+    /// Must be called from a transactional context, the new bid is reserved after the previous one is released.
+    pub(crate) fn do_bid_on_auction(bidder: T::AccountId, auction_id: AuctionId, amount: BalanceOf<T>) -> DispatchResult {
+        let mut auction = Auctions::<T>::get(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+        let now = frame_system::Module::<T>::block_number();
+        ensure!(now < auction.end, Error::<T>::AuctionEnded);
+        ensure!(bidder != auction.seller, Error::<T>::CannotBidOnOwnAuction);
+
+        let minimum = match &auction.highest_bid {
+            Some((_, highest)) => highest.saturating_add(auction.min_increment).max(highest.saturating_add(One::one())),
+            None => auction.reserve_price,
+        };
+        ensure!(amount >= minimum, Error::<T>::BidTooLow);
+
+        if let Some((previous_bidder, previous_amount)) = auction.highest_bid.take() {
+            T::Currency::unreserve(&previous_bidder, previous_amount);
+        }
+        T::Currency::reserve(&bidder, amount)?;
+        auction.highest_bid = Some((bidder.clone(), amount));
+
+        // Bids in the last moments push the end back so others get a chance to respond
+        let extended_end = now.saturating_add(T::AuctionExtendPeriod::get());
+        if extended_end > auction.end {
+            AuctionEndings::<T>::mutate(auction.end, |endings| endings.retain(|id| *id != auction_id));
+            AuctionEndings::<T>::mutate(extended_end, |endings| endings.push(auction_id));
+            auction.end = extended_end;
+            Self::deposit_event(RawEvent::AuctionExtended(auction_id, extended_end));
+        }
+
+        Auctions::<T>::insert(auction_id, auction);
+        Self::deposit_event(RawEvent::AuctionBid(auction_id, bidder, amount));
+        Ok(())
+    }
+
+    pub(crate) fn do_cancel_auction(seller: T::AccountId, auction_id: AuctionId) -> DispatchResult {
+        let auction = Auctions::<T>::get(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+        ensure!(auction.seller == seller, Error::<T>::NotAuctionSeller);
+        ensure!(auction.highest_bid.is_none(), Error::<T>::AuctionHasBids);
+
+        Self::unlock(&seller, auction.domain_id, auction.token_id, auction.quantity);
+        AuctionEndings::<T>::mutate(auction.end, |endings| endings.retain(|id| *id != auction_id));
+        Auctions::<T>::remove(auction_id);
+        Self::deposit_event(RawEvent::AuctionCancelled(auction_id));
+        Ok(())
+    }
+
+    /// Settles at most `MaxAuctionsEndingPerBlock` auctions ending at `now`, anything over that is
+    /// carried to the next block.
+    pub(crate) fn settle_auctions(now: T::BlockNumber) -> Weight {
+        let mut ending = AuctionEndings::<T>::take(now);
+        let mut weight = 0;
+        let max = T::MaxAuctionsEndingPerBlock::get() as usize;
+        if ending.len() > max {
+            let carried = ending.split_off(max);
+            AuctionEndings::<T>::mutate(now.saturating_add(One::one()), |endings| endings.extend(carried));
+            weight = T::DbWeight::get().reads_writes(1, 1);
+        }
+
+        let count = ending.len() as u32;
+        for auction_id in ending {
+            if Self::settle_auction(auction_id).is_err() {
+                Self::abort_auction(auction_id);
+            }
+        }
+        weight.saturating_add(T::WeightInfo::on_initialize(count))
+    }
+
+    fn settle_auction(auction_id: AuctionId) -> DispatchResult {
+        with_transaction_result(|| {
+            let auction = Auctions::<T>::take(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+            Self::unlock(&auction.seller, auction.domain_id, auction.token_id, auction.quantity);
+            match auction.highest_bid {
+                Some((winner, amount)) => {
+                    Self::pay_for_sale(&winner, &auction.seller, auction.domain_id, auction.token_id, amount, true)?;
+                    Self::do_transfer(auction.seller, winner.clone(), auction.domain_id, auction.token_id, auction.quantity)?;
+                    Self::deposit_event(RawEvent::AuctionSettled(auction_id, Some(winner), amount));
+                },
+                None => Self::deposit_event(RawEvent::AuctionSettled(auction_id, None, Zero::zero())),
+            }
+            Ok(())
+        })
+    }
+
+    /// Hands everything back when an auction can't be settled.
+    fn abort_auction(auction_id: AuctionId) {
+        if let Some(auction) = Auctions::<T>::take(auction_id) {
+            Self::unlock(&auction.seller, auction.domain_id, auction.token_id, auction.quantity);
+            if let Some((bidder, amount)) = auction.highest_bid {
+                T::Currency::unreserve(&bidder, amount);
+            }
+            Self::deposit_event(RawEvent::AuctionCancelled(auction_id));
+        }
+    }
+}
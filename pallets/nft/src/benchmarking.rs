@@ -0,0 +1,616 @@
+//! Benchmarks for the nft pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+// The tests `benchmarks!` generates bound `T` in both the generics and a where clause
+#![allow(clippy::multiple_bound_locations)]
+
+use super::*;
+
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{StorageDoubleMap, StorageMap, StorageValue};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+const SUPPLY: u32 = 1_000;
+
+fn funded<T: Trait>(who: T::AccountId) -> T::AccountId {
+    T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 4u32.into());
+    who
+}
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+    funded::<T>(account(name, index, SEED))
+}
+
+fn funded_caller<T: Trait>() -> T::AccountId {
+    funded::<T>(whitelisted_caller())
+}
+
+/// A domain owned by `owner` with one token of `SUPPLY` minted to them
+fn domain_with_token<T: Trait>(owner: &T::AccountId) -> (T::DomainId, T::TokenId) {
+    let domain_id = Module::<T>::do_create_domain(owner.clone(), b"ZOD".to_vec(), b"Zodiac".to_vec()).unwrap();
+    let token_id = Module::<T>::do_create_token(domain_id, owner.clone(), SUPPLY.into(), b"https://zod.io".to_vec(), Permill::from_percent(5), None).unwrap();
+    (domain_id, token_id)
+}
+
+/// A token held by `holder` whose creator takes a royalty on sales
+fn token_held_by<T: Trait>(holder: &T::AccountId) -> (T::DomainId, T::TokenId) {
+    let owner = funded_account::<T>("owner", 0);
+    let (domain_id, token_id) = domain_with_token::<T>(&owner);
+    Module::<T>::do_transfer(owner, holder.clone(), domain_id, token_id, (SUPPLY / 2).into()).unwrap();
+    (domain_id, token_id)
+}
+
+/// Puts another holder after `holder` and returns `holder`'s balance. Moving all of it is the
+/// worst case for the indexes, `holder` is dropped and the one behind moved into the gap.
+fn drained_balance<T: Trait>(holder: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) -> T::Balance {
+    Module::<T>::do_transfer(holder.clone(), account("behind", 0, SEED), domain_id, token_id, One::one()).unwrap();
+    Balances::<T>::get(holder, (domain_id, token_id))
+}
+
+fn empty_token<T: Trait>(owner: &T::AccountId, domain_id: T::DomainId) -> T::TokenId {
+    let token_id = Module::<T>::do_create_token(domain_id, owner.clone(), SUPPLY.into(), b"https://zod.io".to_vec(), Permill::zero(), None).unwrap();
+    Module::<T>::do_burn(owner.clone(), domain_id, token_id, SUPPLY.into()).unwrap();
+    token_id
+}
+
+/// Leaves `a` approvals on a token, one of each kind and the rest open bids. Bids cost the most
+/// to clear, each is unreserved and reported.
+fn approvals_on<T: Trait>(domain_id: T::DomainId, token_id: T::TokenId, a: u32) {
+    let holder: T::AccountId = account("holder", 0, SEED);
+    let key = (domain_id, token_id);
+    for i in 0 .. a {
+        let approved: T::AccountId = account("approved", i, SEED);
+        match i {
+            0 => Approvals::<T>::insert(key, (holder.clone(), approved), true),
+            1 => Allowances::<T>::insert(key, (holder.clone(), approved), Allowance { amount: One::one(), expires_at: None }),
+            2 => Frozen::<T>::insert(key, approved, true),
+            _ => {
+                let bidder = funded::<T>(approved);
+                T::Currency::reserve(&bidder, 100u32.into()).unwrap();
+                Bids::<T>::insert(key, i as BidId, Bid { bidder, seller: holder.clone(), quantity: One::one(), price: 100u32.into() });
+            },
+        }
+    }
+}
+
+benchmarks! {
+    _ { }
+
+    add_number {
+        let caller = funded_caller::<T>();
+    }: _(RawOrigin::Signed(caller.clone()), Zero::zero(), Zero::zero(), One::one())
+    verify {
+        assert_eq!(Numbers::<T>::get(&caller, (T::DomainId::zero(), T::TokenId::zero())), One::one());
+    }
+
+    create_domain {
+        let caller = funded_caller::<T>();
+        let domain_id = NextDomainId::<T>::get();
+    }: _(RawOrigin::Signed(caller.clone()), b"ZOD".to_vec(), b"Zodiac".to_vec())
+    verify {
+        assert_eq!(Domains::<T>::get(domain_id).owner, caller);
+    }
+
+    create_token {
+        let caller = funded_caller::<T>();
+        let domain_id = Module::<T>::do_create_domain(caller.clone(), b"ZOD".to_vec(), b"Zodiac".to_vec())?;
+        let max_supply: Option<T::Balance> = Some((SUPPLY * 2).into());
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, caller.clone(), SUPPLY.into(), b"https://zod.io".to_vec(), Permill::from_percent(5), max_supply)
+    verify {
+        assert_eq!(Balances::<T>::get(&caller, (domain_id, T::TokenId::one())), SUPPLY.into());
+    }
+
+    mint_tokens {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, recipient.clone(), 10u32.into())
+    verify {
+        assert_eq!(Balances::<T>::get(&recipient, (domain_id, token_id)), 10u32.into());
+    }
+
+    set_max_supply {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, (SUPPLY * 2).into())
+    verify {
+        assert_eq!(Tokens::<T>::get(domain_id, token_id).max_supply, Some((SUPPLY * 2).into()));
+    }
+
+    freeze_supply {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id)
+    verify {
+        assert_eq!(Tokens::<T>::get(domain_id, token_id).max_supply, Some(SUPPLY.into()));
+    }
+
+    set_royalty {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, Permill::from_percent(1))
+    verify {
+        assert_eq!(Tokens::<T>::get(domain_id, token_id).royalty, Permill::from_percent(1));
+    }
+
+    set_transfer_policy {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, TransferPolicy::Soulbound)
+    verify {
+        assert_eq!(Tokens::<T>::get(domain_id, token_id).transfer_policy, TransferPolicy::Soulbound);
+    }
+
+    freeze {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let holder: T::AccountId = account("holder", 0, SEED);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, holder.clone())
+    verify {
+        assert!(Frozen::<T>::get((domain_id, token_id), &holder));
+    }
+
+    thaw {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let holder: T::AccountId = account("holder", 0, SEED);
+        Frozen::<T>::insert((domain_id, token_id), &holder, true);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, holder.clone())
+    verify {
+        assert!(!Frozen::<T>::get((domain_id, token_id), &holder));
+    }
+
+    burn_tokens {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let quantity = drained_balance::<T>(&caller, domain_id, token_id);
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, token_id, caller.clone(), quantity)
+    verify {
+        assert_eq!(Tokens::<T>::get(domain_id, token_id).burned, quantity);
+    }
+
+    burn_own_tokens {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        let quantity = drained_balance::<T>(&caller, domain_id, token_id);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, quantity)
+    verify {
+        assert_eq!(Tokens::<T>::get(domain_id, token_id).burned, quantity);
+    }
+
+    transfer {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        let quantity = drained_balance::<T>(&caller, domain_id, token_id);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, recipient.clone(), quantity)
+    verify {
+        assert_eq!(Balances::<T>::get(&recipient, (domain_id, token_id)), quantity);
+    }
+
+    transfer_batch {
+        let n in 1 .. 100;
+        let caller = funded_caller::<T>();
+        // Every transfer empties one of the caller's tokens
+        let mut transfers: Vec<BatchTransfer<T>> = Vec::new();
+        for i in 0 .. n {
+            let (domain_id, token_id) = token_held_by::<T>(&caller);
+            let quantity = drained_balance::<T>(&caller, domain_id, token_id);
+            transfers.push((domain_id, token_id, account("recipient", i, SEED), quantity));
+        }
+        let (domain_id, token_id, recipient, quantity) = transfers[n as usize - 1].clone();
+    }: _(RawOrigin::Signed(caller), transfers)
+    verify {
+        assert_eq!(Balances::<T>::get(&recipient, (domain_id, token_id)), quantity);
+    }
+
+    set_approval_for_all {
+        let caller = funded_caller::<T>();
+        let (domain_id, _) = token_held_by::<T>(&caller);
+        let operator: T::AccountId = account("operator", 0, SEED);
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, operator.clone(), true)
+    verify {
+        assert!(Operators::<T>::get(domain_id, (caller, operator)));
+    }
+
+    set_approval_for_tokens {
+        let n in 1 .. 100;
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        let operator: T::AccountId = account("operator", 0, SEED);
+        // Approvals can only be given for tokens that exist
+        let owner = Domains::<T>::get(domain_id).owner;
+        let mut token_ids = vec![token_id];
+        for _ in 1 .. n {
+            token_ids.push(Module::<T>::do_create_token(domain_id, owner.clone(), SUPPLY.into(), b"https://zod.io".to_vec(), Permill::zero(), None)?);
+        }
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, token_ids, operator.clone(), true)
+    verify {
+        assert!(Approvals::<T>::get((domain_id, token_id), (caller, operator)));
+    }
+
+    transfer_from {
+        let caller = funded_caller::<T>();
+        let holder = funded_account::<T>("holder", 0);
+        let (domain_id, token_id) = token_held_by::<T>(&holder);
+        let quantity = drained_balance::<T>(&holder, domain_id, token_id);
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        // Spending an allowance is the slower path
+        Module::<T>::set_allowance(&holder, &caller, domain_id, token_id, Allowance { amount: quantity, expires_at: None });
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, holder, recipient.clone(), quantity)
+    verify {
+        assert_eq!(Balances::<T>::get(&recipient, (domain_id, token_id)), quantity);
+    }
+
+    approve {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let expires_at: Option<T::BlockNumber> = Some(100u32.into());
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, token_id, spender.clone(), 10u32.into(), expires_at)
+    verify {
+        assert_eq!(Module::<T>::allowance(&caller, &spender, domain_id, token_id), 10u32.into());
+    }
+
+    increase_allowance {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        let spender: T::AccountId = account("spender", 0, SEED);
+        Module::<T>::set_allowance(&caller, &spender, domain_id, token_id, Allowance { amount: 10u32.into(), expires_at: None });
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, token_id, spender.clone(), 10u32.into())
+    verify {
+        assert_eq!(Module::<T>::allowance(&caller, &spender, domain_id, token_id), 20u32.into());
+    }
+
+    decrease_allowance {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        let spender: T::AccountId = account("spender", 0, SEED);
+        Module::<T>::set_allowance(&caller, &spender, domain_id, token_id, Allowance { amount: 20u32.into(), expires_at: None });
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, token_id, spender.clone(), 10u32.into())
+    verify {
+        assert_eq!(Module::<T>::allowance(&caller, &spender, domain_id, token_id), 10u32.into());
+    }
+
+    list_tokens {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, token_id, 10u32.into(), 100u32.into())
+    verify {
+        assert!(Listings::<T>::get((domain_id, token_id), &caller).is_some());
+    }
+
+    cancel_listing {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        Module::<T>::do_list(caller.clone(), domain_id, token_id, 10u32.into(), 100u32.into())?;
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, token_id)
+    verify {
+        assert!(Listings::<T>::get((domain_id, token_id), &caller).is_none());
+    }
+
+    buy_tokens {
+        let caller = funded_caller::<T>();
+        let seller = funded_account::<T>("seller", 0);
+        let (domain_id, token_id) = token_held_by::<T>(&seller);
+        Module::<T>::do_list(seller.clone(), domain_id, token_id, 10u32.into(), 100u32.into())?;
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, token_id, seller, 10u32.into())
+    verify {
+        assert_eq!(Balances::<T>::get(&caller, (domain_id, token_id)), 10u32.into());
+    }
+
+    place_bid {
+        let caller = funded_caller::<T>();
+        let seller = funded_account::<T>("seller", 0);
+        let (domain_id, token_id) = token_held_by::<T>(&seller);
+        Module::<T>::do_list(seller.clone(), domain_id, token_id, 10u32.into(), 100u32.into())?;
+        let bid_id = NextBidId::get();
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, seller, 10u32.into(), 50u32.into())
+    verify {
+        assert!(Bids::<T>::get((domain_id, token_id), bid_id).is_some());
+    }
+
+    accept_bid {
+        let caller = funded_caller::<T>();
+        let bidder = funded_account::<T>("bidder", 0);
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        Module::<T>::do_list(caller.clone(), domain_id, token_id, 10u32.into(), 100u32.into())?;
+        let bid_id = Module::<T>::do_place_bid(bidder.clone(), domain_id, token_id, caller.clone(), 10u32.into(), 50u32.into())?.unwrap();
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, bid_id)
+    verify {
+        assert_eq!(Balances::<T>::get(&bidder, (domain_id, token_id)), 10u32.into());
+    }
+
+    reject_bid {
+        let caller = funded_caller::<T>();
+        let bidder = funded_account::<T>("bidder", 0);
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        Module::<T>::do_list(caller.clone(), domain_id, token_id, 10u32.into(), 100u32.into())?;
+        let bid_id = Module::<T>::do_place_bid(bidder, domain_id, token_id, caller.clone(), 10u32.into(), 50u32.into())?.unwrap();
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, bid_id)
+    verify {
+        assert!(Bids::<T>::get((domain_id, token_id), bid_id).is_none());
+    }
+
+    cancel_bid {
+        let caller = funded_caller::<T>();
+        let seller = funded_account::<T>("seller", 0);
+        let (domain_id, token_id) = token_held_by::<T>(&seller);
+        Module::<T>::do_list(seller.clone(), domain_id, token_id, 10u32.into(), 100u32.into())?;
+        let bid_id = Module::<T>::do_place_bid(caller.clone(), domain_id, token_id, seller, 10u32.into(), 50u32.into())?.unwrap();
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, bid_id)
+    verify {
+        assert!(Bids::<T>::get((domain_id, token_id), bid_id).is_none());
+    }
+
+    create_auction {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        let end = frame_system::Module::<T>::block_number() + 10u32.into();
+        let auction_id = NextAuctionId::get();
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, 10u32.into(), 100u32.into(), 10u32.into(), end)
+    verify {
+        assert!(Auctions::<T>::get(auction_id).is_some());
+    }
+
+    bid_on_auction {
+        let caller = funded_caller::<T>();
+        let seller = funded_account::<T>("seller", 0);
+        let bidder = funded_account::<T>("bidder", 0);
+        let (domain_id, token_id) = token_held_by::<T>(&seller);
+        // Outbidding someone right before the end refunds them and extends the auction
+        let end = frame_system::Module::<T>::block_number() + One::one();
+        let auction_id = Module::<T>::do_create_auction(seller, domain_id, token_id, 10u32.into(), 100u32.into(), 10u32.into(), end)?;
+        Module::<T>::do_bid_on_auction(bidder, auction_id, 100u32.into())?;
+    }: _(RawOrigin::Signed(caller.clone()), auction_id, 200u32.into())
+    verify {
+        assert_eq!(Auctions::<T>::get(auction_id).unwrap().highest_bid, Some((caller, 200u32.into())));
+    }
+
+    cancel_auction {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = token_held_by::<T>(&caller);
+        let end = frame_system::Module::<T>::block_number() + 10u32.into();
+        let auction_id = Module::<T>::do_create_auction(caller.clone(), domain_id, token_id, 10u32.into(), 100u32.into(), 10u32.into(), end)?;
+    }: _(RawOrigin::Signed(caller), auction_id)
+    verify {
+        assert!(Auctions::<T>::get(auction_id).is_none());
+    }
+
+    on_initialize {
+        let n in 0 .. T::MaxAuctionsEndingPerBlock::get();
+        let end = frame_system::Module::<T>::block_number() + T::AuctionExtendPeriod::get() + One::one();
+        let first_id = NextAuctionId::get();
+        // Each winner pays the seller and a royalty and takes everything the seller had
+        for i in 0 .. n {
+            let seller = funded_account::<T>("seller", i);
+            let (domain_id, token_id) = token_held_by::<T>(&seller);
+            let quantity = drained_balance::<T>(&seller, domain_id, token_id);
+            let auction_id = Module::<T>::do_create_auction(seller, domain_id, token_id, quantity, 100u32.into(), 10u32.into(), end)?;
+            Module::<T>::do_bid_on_auction(funded_account::<T>("bidder", i), auction_id, 100u32.into())?;
+        }
+    }: {
+        Module::<T>::settle_auctions(end);
+    }
+    verify {
+        assert!((first_id .. first_id + n as AuctionId).all(|auction_id| Auctions::<T>::get(auction_id).is_none()));
+    }
+
+    create_dutch_auction {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let auction_id = NextAuctionId::get();
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, 10u32.into(), 1000u32.into(), 100u32.into(), 10u32.into())
+    verify {
+        assert!(DutchAuctions::<T>::get(auction_id).is_some());
+    }
+
+    buy_from_dutch_auction {
+        let caller = funded_caller::<T>();
+        let owner = funded_account::<T>("owner", 0);
+        let (domain_id, token_id) = domain_with_token::<T>(&owner);
+        let auction_id = Module::<T>::do_create_dutch_auction(owner, domain_id, token_id, 10u32.into(), 1000u32.into(), 100u32.into(), 10u32.into())?;
+    }: _(RawOrigin::Signed(caller.clone()), auction_id, 5u32.into(), 1000u32.into())
+    verify {
+        assert_eq!(Balances::<T>::get(&caller, (domain_id, token_id)), 5u32.into());
+    }
+
+    close_dutch_auction {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let auction_id = Module::<T>::do_create_dutch_auction(caller.clone(), domain_id, token_id, 10u32.into(), 1000u32.into(), 100u32.into(), 10u32.into())?;
+    }: _(RawOrigin::Signed(caller), auction_id)
+    verify {
+        assert!(DutchAuctions::<T>::get(auction_id).is_none());
+    }
+
+    transfer_domain_ownership {
+        let caller = funded_caller::<T>();
+        let (domain_id, _) = domain_with_token::<T>(&caller);
+        let new_owner: T::AccountId = account("new_owner", 0, SEED);
+    }: _(RawOrigin::Signed(caller), domain_id, new_owner.clone())
+    verify {
+        assert_eq!(PendingDomainOwners::<T>::get(domain_id), Some(new_owner));
+    }
+
+    accept_domain_ownership {
+        let r in 0 .. 100;
+        let caller = funded_caller::<T>();
+        let owner = funded_account::<T>("owner", 0);
+        let (domain_id, _) = domain_with_token::<T>(&owner);
+        for i in 0 .. r {
+            Roles::<T>::insert(domain_id, (account::<T::AccountId>("minter", i, SEED), Role::Minter), true);
+        }
+        PendingDomainOwners::<T>::insert(domain_id, caller.clone());
+    }: _(RawOrigin::Signed(caller.clone()), domain_id, r)
+    verify {
+        assert_eq!(Domains::<T>::get(domain_id).owner, caller);
+        assert!(Roles::<T>::iter_prefix(domain_id).next().is_none());
+    }
+
+    cancel_domain_ownership_transfer {
+        let caller = funded_caller::<T>();
+        let (domain_id, _) = domain_with_token::<T>(&caller);
+        PendingDomainOwners::<T>::insert(domain_id, account::<T::AccountId>("new_owner", 0, SEED));
+    }: _(RawOrigin::Signed(caller), domain_id)
+    verify {
+        assert!(PendingDomainOwners::<T>::get(domain_id).is_none());
+    }
+
+    set_attribute {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let key = vec![0u8; T::KeyLimit::get() as usize];
+        let value = vec![0u8; T::ValueLimit::get() as usize];
+    }: _(RawOrigin::Signed(caller), domain_id, Some(token_id), key.clone(), value)
+    verify {
+        assert!(Attributes::<T>::get((domain_id, Some(token_id)), &key).is_some());
+    }
+
+    clear_attribute {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let key = vec![0u8; T::KeyLimit::get() as usize];
+        Module::<T>::do_set_attribute(domain_id, Some(token_id), key.clone(), vec![0u8; T::ValueLimit::get() as usize])?;
+    }: _(RawOrigin::Signed(caller), domain_id, Some(token_id), key.clone())
+    verify {
+        assert!(Attributes::<T>::get((domain_id, Some(token_id)), &key).is_none());
+    }
+
+    set_token_uri {
+        let caller = funded_caller::<T>();
+        let (domain_id, token_id) = domain_with_token::<T>(&caller);
+        let uri = Some(vec![0u8; T::ValueLimit::get() as usize]);
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, uri.clone())
+    verify {
+        assert_eq!(Tokens::<T>::get(domain_id, token_id).uri, uri);
+    }
+
+    set_uri_template {
+        let caller = funded_caller::<T>();
+        let (domain_id, _) = domain_with_token::<T>(&caller);
+        let uri_template = Some(vec![0u8; T::ValueLimit::get() as usize]);
+    }: _(RawOrigin::Signed(caller), domain_id, uri_template.clone())
+    verify {
+        assert_eq!(Domains::<T>::get(domain_id).uri_template, uri_template);
+    }
+
+    destroy_token {
+        let a in 0 .. 100;
+        let r in 0 .. 100;
+        let caller = funded_caller::<T>();
+        let (domain_id, _) = domain_with_token::<T>(&caller);
+        let token_id = empty_token::<T>(&caller, domain_id);
+        approvals_on::<T>(domain_id, token_id, a);
+        for i in 0 .. r {
+            Module::<T>::do_set_attribute(domain_id, Some(token_id), i.encode(), b"value".to_vec())?;
+        }
+        let witness = DestroyWitness { tokens: 1, approvals: a, attributes: r };
+    }: _(RawOrigin::Signed(caller), domain_id, token_id, witness)
+    verify {
+        assert!(!Tokens::<T>::contains_key(domain_id, token_id));
+    }
+
+    destroy_domain {
+        let t in 0 .. 50;
+        let a in 0 .. 100;
+        let r in 0 .. 100;
+        let caller = funded_caller::<T>();
+        let domain_id = Module::<T>::do_create_domain(caller.clone(), b"ZOD".to_vec(), b"Zodiac".to_vec())?;
+        for _ in 0..t {
+            empty_token::<T>(&caller, domain_id);
+        }
+        // The approvals sit on one more token, as token approvals and bids are dearer to clear
+        // than operators and roles
+        let token_id = empty_token::<T>(&caller, domain_id);
+        approvals_on::<T>(domain_id, token_id, a);
+        for i in 0..r {
+            Module::<T>::do_set_attribute(domain_id, None, i.encode(), b"value".to_vec())?;
+        }
+        let witness = DestroyWitness { tokens: t + 1, approvals: a, attributes: r };
+    }: _(RawOrigin::Signed(caller), domain_id, witness)
+    verify {
+        assert!(!Domains::<T>::contains_key(domain_id));
+    }
+
+    grant_role {
+        let caller = funded_caller::<T>();
+        let (domain_id, _) = domain_with_token::<T>(&caller);
+        let minter: T::AccountId = account("minter", 0, SEED);
+    }: _(RawOrigin::Signed(caller), domain_id, minter.clone(), Role::Minter)
+    verify {
+        assert!(Roles::<T>::get(domain_id, (minter, Role::Minter)));
+    }
+
+    revoke_role {
+        let caller = funded_caller::<T>();
+        let (domain_id, _) = domain_with_token::<T>(&caller);
+        let minter: T::AccountId = account("minter", 0, SEED);
+        Roles::<T>::insert(domain_id, (minter.clone(), Role::Minter), true);
+    }: _(RawOrigin::Signed(caller), domain_id, minter.clone(), Role::Minter)
+    verify {
+        assert!(!Roles::<T>::get(domain_id, (minter, Role::Minter)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{new_test_ext, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn benchmarks_run_against_the_mock() {
+        new_test_ext().execute_with(|| {
+            assert_ok!(test_benchmark_add_number::<Test>());
+            assert_ok!(test_benchmark_create_domain::<Test>());
+            assert_ok!(test_benchmark_create_token::<Test>());
+            assert_ok!(test_benchmark_mint_tokens::<Test>());
+            assert_ok!(test_benchmark_set_max_supply::<Test>());
+            assert_ok!(test_benchmark_freeze_supply::<Test>());
+            assert_ok!(test_benchmark_set_royalty::<Test>());
+            assert_ok!(test_benchmark_set_transfer_policy::<Test>());
+            assert_ok!(test_benchmark_freeze::<Test>());
+            assert_ok!(test_benchmark_thaw::<Test>());
+            assert_ok!(test_benchmark_burn_tokens::<Test>());
+            assert_ok!(test_benchmark_burn_own_tokens::<Test>());
+            assert_ok!(test_benchmark_transfer::<Test>());
+            assert_ok!(test_benchmark_transfer_batch::<Test>());
+            assert_ok!(test_benchmark_set_approval_for_all::<Test>());
+            assert_ok!(test_benchmark_set_approval_for_tokens::<Test>());
+            assert_ok!(test_benchmark_transfer_from::<Test>());
+            assert_ok!(test_benchmark_approve::<Test>());
+            assert_ok!(test_benchmark_increase_allowance::<Test>());
+            assert_ok!(test_benchmark_decrease_allowance::<Test>());
+            assert_ok!(test_benchmark_list_tokens::<Test>());
+            assert_ok!(test_benchmark_cancel_listing::<Test>());
+            assert_ok!(test_benchmark_buy_tokens::<Test>());
+            assert_ok!(test_benchmark_place_bid::<Test>());
+            assert_ok!(test_benchmark_accept_bid::<Test>());
+            assert_ok!(test_benchmark_reject_bid::<Test>());
+            assert_ok!(test_benchmark_cancel_bid::<Test>());
+            assert_ok!(test_benchmark_create_auction::<Test>());
+            assert_ok!(test_benchmark_bid_on_auction::<Test>());
+            assert_ok!(test_benchmark_cancel_auction::<Test>());
+            assert_ok!(test_benchmark_on_initialize::<Test>());
+            assert_ok!(test_benchmark_create_dutch_auction::<Test>());
+            assert_ok!(test_benchmark_buy_from_dutch_auction::<Test>());
+            assert_ok!(test_benchmark_close_dutch_auction::<Test>());
+            assert_ok!(test_benchmark_transfer_domain_ownership::<Test>());
+            assert_ok!(test_benchmark_accept_domain_ownership::<Test>());
+            assert_ok!(test_benchmark_cancel_domain_ownership_transfer::<Test>());
+            assert_ok!(test_benchmark_set_attribute::<Test>());
+            assert_ok!(test_benchmark_clear_attribute::<Test>());
+            assert_ok!(test_benchmark_set_token_uri::<Test>());
+            assert_ok!(test_benchmark_set_uri_template::<Test>());
+            assert_ok!(test_benchmark_destroy_token::<Test>());
+            assert_ok!(test_benchmark_destroy_domain::<Test>());
+            assert_ok!(test_benchmark_grant_role::<Test>());
+            assert_ok!(test_benchmark_revoke_role::<Test>());
+        });
+    }
+}
@@ -0,0 +1,58 @@
+use frame_support::{
+    dispatch::{result::Result, DispatchError, DispatchResult},
+    traits::{Get, ReservableCurrency},
+    StorageDoubleMap, StorageMap,
+};
+use sp_runtime::traits::{Saturating, Zero};
+
+use crate::{BalanceOf, DomainDeposits, Deposits, Domains, Module, Tokens, Trait};
+
+impl<T: Trait> Module<T> {
+    pub(crate) fn deposit_for(base: BalanceOf<T>, bytes: usize) -> BalanceOf<T> {
+        T::DepositPerByte::get()
+            .saturating_mul((bytes as u32).into())
+            .saturating_add(base)
+    }
+
+    pub(crate) fn domain_deposit(domain_id: T::DomainId) -> BalanceOf<T> {
+        let domain = Domains::<T>::get(domain_id);
+        let bytes = domain.symbol.len() + domain.name.len() + domain.uri_template.map_or(0, |template| template.len());
+        Self::deposit_for(T::DomainDepositBase::get(), bytes)
+    }
+
+    pub(crate) fn token_deposit(domain_id: T::DomainId, token_id: T::TokenId) -> BalanceOf<T> {
+        let token = Tokens::<T>::get(domain_id, token_id);
+        let bytes = token.base_uri.len() + token.uri.map_or(0, |uri| uri.len());
+        Self::deposit_for(T::TokenDepositBase::get(), bytes)
+    }
+
+    /// Bring the deposit held for a domain (`None`) or one of its tokens up or down to what it stores now
+    pub(crate) fn update_deposit(domain_id: T::DomainId, token_id: Option<T::TokenId>) -> DispatchResult {
+        let deposit = match token_id {
+            Some(token_id) => Self::token_deposit(domain_id, token_id),
+            None => Self::domain_deposit(domain_id),
+        };
+        let old_deposit = Deposits::<T>::get(domain_id, token_id);
+        Self::adjust_domain_deposit(domain_id, old_deposit, deposit)?;
+        Deposits::<T>::insert(domain_id, token_id, deposit);
+        Ok(())
+    }
+
+    /// Release the deposit held for a domain (`None`) or one of its tokens
+    pub(crate) fn release_deposit(domain_id: T::DomainId, token_id: Option<T::TokenId>) -> DispatchResult {
+        let deposit = Deposits::<T>::take(domain_id, token_id);
+        Self::adjust_domain_deposit(domain_id, deposit, Zero::zero())
+    }
+
+    /// Deposits are held by the domain owner, moving from `old` to `new`
+    pub(crate) fn adjust_domain_deposit(domain_id: T::DomainId, old: BalanceOf<T>, new: BalanceOf<T>) -> Result<(), DispatchError> {
+        let owner = Domains::<T>::get(domain_id).owner;
+        if new > old {
+            T::Currency::reserve(&owner, new - old)?;
+        } else {
+            T::Currency::unreserve(&owner, old - new);
+        }
+        DomainDeposits::<T>::mutate(domain_id, |total| *total = total.saturating_add(new).saturating_sub(old));
+        Ok(())
+    }
+}
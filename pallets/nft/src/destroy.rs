@@ -0,0 +1,116 @@
+use frame_support::{
+    dispatch::{result::Result, DispatchError, DispatchResult},
+    ensure,
+    traits::ReservableCurrency,
+    IterableStorageDoubleMap, StorageDoubleMap, StorageMap,
+};
+use sp_runtime::traits::Zero;
+use sp_std::vec::Vec;
+
+use crate::{
+    Allowances, Approvals, Attributes, Bids, DestroyWitness, DomainDeposits, Deposits, Domains, Error, Frozen, Module, Operators, PendingDomainOwners, RawEvent,
+    Roles, Tokens, Trait,
+};
+
+impl<T: Trait> Module<T> {
+    /// The witness has to cover everything left on the token, its `tokens` isn't used.
+    /// Must be called from a transactional context, a witness that falls short fails part way.
+    pub(crate) fn do_destroy_token(domain_id: T::DomainId, token_id: T::TokenId, witness: DestroyWitness) -> DispatchResult {
+        ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+        let mut budget = DestroyWitness { tokens: 1, ..witness };
+        ensure!(Self::clear_token(domain_id, token_id, &mut budget)?, Error::<T>::BadWitness);
+        Ok(())
+    }
+
+    /// Removes what the witness allows of the domain, finishing it off once nothing is left.
+    /// Tokens must be empty before they can go.
+    pub(crate) fn do_destroy_domain(who: T::AccountId, domain_id: T::DomainId, witness: DestroyWitness) -> DispatchResult {
+        ensure!(Domains::<T>::contains_key(domain_id), Error::<T>::InvalidDomain);
+        let domain = Domains::<T>::get(domain_id);
+        ensure!(domain.owner == who, Error::<T>::NotDomainOwner);
+        if !domain.destroying {
+            Domains::<T>::mutate(domain_id, |domain| domain.destroying = true);
+        }
+
+        let mut budget = witness;
+        for (key, _) in Self::take_within(Operators::<T>::iter_prefix(domain_id), &mut budget.approvals) {
+            Operators::<T>::remove(domain_id, key);
+        }
+        for (key, _) in Self::take_within(Roles::<T>::iter_prefix(domain_id), &mut budget.approvals) {
+            Roles::<T>::remove(domain_id, key);
+        }
+        Self::clear_attributes(domain_id, None, &mut budget.attributes)?;
+        let token_ids: Vec<T::TokenId> = Tokens::<T>::iter_prefix(domain_id).map(|(token_id, _)| token_id).take(witness.tokens as usize).collect();
+        for token_id in token_ids {
+            if !Self::clear_token(domain_id, token_id, &mut budget)? {
+                break;
+            }
+        }
+
+        let finished = Operators::<T>::iter_prefix(domain_id).next().is_none()
+            && Roles::<T>::iter_prefix(domain_id).next().is_none()
+            && Attributes::<T>::iter_prefix((domain_id, None::<T::TokenId>)).next().is_none()
+            && Tokens::<T>::iter_prefix(domain_id).next().is_none();
+        if finished {
+            Deposits::<T>::remove(domain_id, None::<T::TokenId>);
+            T::Currency::unreserve(&domain.owner, DomainDeposits::<T>::take(domain_id));
+            PendingDomainOwners::<T>::remove(domain_id);
+            Domains::<T>::remove(domain_id);
+            Self::deposit_event(RawEvent::DomainDestroyed(domain_id));
+        }
+        Ok(())
+    }
+
+    /// Returns whether the token is gone, or false when the budget ran out first
+    fn clear_token(domain_id: T::DomainId, token_id: T::TokenId, budget: &mut DestroyWitness) -> Result<bool, DispatchError> {
+        ensure!(Tokens::<T>::get(domain_id, token_id).total_supply.is_zero(), Error::<T>::TokenNotEmpty);
+        if budget.tokens == 0 {
+            return Ok(false);
+        }
+
+        let key = (domain_id, token_id);
+        for (approval, _) in Self::take_within(Approvals::<T>::iter_prefix(key), &mut budget.approvals) {
+            Approvals::<T>::remove(key, approval);
+        }
+        for (allowance, _) in Self::take_within(Allowances::<T>::iter_prefix(key), &mut budget.approvals) {
+            Allowances::<T>::remove(key, allowance);
+        }
+        for (account, _) in Self::take_within(Frozen::<T>::iter_prefix(key), &mut budget.approvals) {
+            Frozen::<T>::remove(key, account);
+        }
+        for (bid_id, bid) in Self::take_within(Bids::<T>::iter_prefix(key), &mut budget.approvals) {
+            T::Currency::unreserve(&bid.bidder, Self::price_for(bid.quantity, bid.price)?);
+            Bids::<T>::remove(key, bid_id);
+            Self::deposit_event(RawEvent::BidCancelled(bid_id, domain_id, token_id));
+        }
+        Self::clear_attributes(domain_id, Some(token_id), &mut budget.attributes)?;
+
+        let cleared = Approvals::<T>::iter_prefix(key).next().is_none()
+            && Allowances::<T>::iter_prefix(key).next().is_none()
+            && Frozen::<T>::iter_prefix(key).next().is_none()
+            && Bids::<T>::iter_prefix(key).next().is_none()
+            && Attributes::<T>::iter_prefix((domain_id, Some(token_id))).next().is_none();
+        if !cleared {
+            return Ok(false);
+        }
+        Self::release_deposit(domain_id, Some(token_id))?;
+        Tokens::<T>::remove(domain_id, token_id);
+        budget.tokens -= 1;
+        Self::deposit_event(RawEvent::TokenDestroyed(domain_id, token_id));
+        Ok(true)
+    }
+
+    fn clear_attributes(domain_id: T::DomainId, token_id: Option<T::TokenId>, budget: &mut u32) -> DispatchResult {
+        for (key, (_, deposit)) in Self::take_within(Attributes::<T>::iter_prefix((domain_id, token_id)), budget) {
+            Attributes::<T>::remove((domain_id, token_id), key);
+            Self::adjust_domain_deposit(domain_id, deposit, Zero::zero())?;
+        }
+        Ok(())
+    }
+
+    fn take_within<I: Iterator>(iter: I, budget: &mut u32) -> Vec<I::Item> {
+        let items: Vec<I::Item> = iter.take(*budget as usize).collect();
+        *budget -= items.len() as u32;
+        items
+    }
+}
@@ -0,0 +1,113 @@
+//! Secondary indexes over `Balances`: the tokens each account holds and the holders of each
+//! token. Both are kept as dense position lists so pages can be read without scanning.
+
+use frame_support::{StorageDoubleMap, StorageMap};
+use sp_std::{cmp, vec::Vec};
+
+use crate::{
+    Balances, HolderCount, HolderPositions, Holders, Module, OwnedTokenCount, OwnedTokenPositions, OwnedTokens, Trait,
+};
+
+/// Most entries a single page query returns
+pub const MAX_PAGE_SIZE: u32 = 256;
+
+/// A page of entries and the cursor of the next page if there is one
+pub type Page<Entry> = (Vec<Entry>, Option<u32>);
+
+impl<T: Trait> Module<T> {
+    /// Records that `who` holds some of the token, a no-op if already recorded
+    pub(crate) fn index_holding(who: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) {
+        let id = (domain_id, token_id);
+        if HolderPositions::<T>::contains_key(id, who) {
+            return;
+        }
+
+        let position = HolderCount::<T>::get(id);
+        Holders::<T>::insert(id, position, who);
+        HolderPositions::<T>::insert(id, who, position);
+        HolderCount::<T>::insert(id, position + 1);
+
+        let position = OwnedTokenCount::<T>::get(who);
+        OwnedTokens::<T>::insert(who, position, id);
+        OwnedTokenPositions::<T>::insert(who, id, position);
+        OwnedTokenCount::<T>::insert(who, position + 1);
+    }
+
+    /// Drops `who` from the holders of the token, moving the last entry of each list into the gap
+    pub(crate) fn unindex_holding(who: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) {
+        let id = (domain_id, token_id);
+
+        if let Some(position) = HolderPositions::<T>::take(id, who) {
+            let last = HolderCount::<T>::get(id).saturating_sub(1);
+            let moved = Holders::<T>::take(id, last);
+            if position != last {
+                if let Some(moved) = moved {
+                    HolderPositions::<T>::insert(id, &moved, position);
+                    Holders::<T>::insert(id, position, moved);
+                }
+            }
+            if last == 0 {
+                HolderCount::<T>::remove(id);
+            } else {
+                HolderCount::<T>::insert(id, last);
+            }
+        }
+
+        if let Some(position) = OwnedTokenPositions::<T>::take(who, id) {
+            let last = OwnedTokenCount::<T>::get(who).saturating_sub(1);
+            let moved = OwnedTokens::<T>::take(who, last);
+            if position != last {
+                if let Some(moved) = moved {
+                    OwnedTokenPositions::<T>::insert(who, moved, position);
+                    OwnedTokens::<T>::insert(who, position, moved);
+                }
+            }
+            if last == 0 {
+                OwnedTokenCount::<T>::remove(who);
+            } else {
+                OwnedTokenCount::<T>::insert(who, last);
+            }
+        }
+    }
+
+    /// Up to `limit` of the tokens `who` holds with their balances, starting at `cursor`, and the
+    /// cursor of the next page if there is one. An empty page never has a next one. Removals
+    /// reorder the list, so pages read across blocks may skip or repeat entries.
+    pub fn tokens_of(who: &T::AccountId, cursor: u32, limit: u32) -> Page<(T::DomainId, T::TokenId, T::Balance)> {
+        let count = OwnedTokenCount::<T>::get(who);
+        let end = Self::page_end(count, cursor, limit);
+        let tokens = (cursor..end)
+            .filter_map(|position| OwnedTokens::<T>::get(who, position))
+            .map(|(domain_id, token_id)| (domain_id, token_id, Balances::<T>::get(who, (domain_id, token_id))))
+            .collect();
+        (tokens, Self::next_cursor(count, cursor, end))
+    }
+
+    /// Up to `limit` of the holders of a token with their balances, starting at `cursor`, and the
+    /// cursor of the next page if there is one.
+    pub fn holders_of(domain_id: T::DomainId, token_id: T::TokenId, cursor: u32, limit: u32) -> Page<(T::AccountId, T::Balance)> {
+        let id = (domain_id, token_id);
+        let count = HolderCount::<T>::get(id);
+        let end = Self::page_end(count, cursor, limit);
+        let holders = (cursor..end)
+            .filter_map(|position| Holders::<T>::get(id, position))
+            .map(|holder| {
+                let balance = Balances::<T>::get(&holder, id);
+                (holder, balance)
+            })
+            .collect();
+        (holders, Self::next_cursor(count, cursor, end))
+    }
+
+    fn page_end(count: u32, cursor: u32, limit: u32) -> u32 {
+        cmp::min(count, cursor.saturating_add(cmp::min(limit, MAX_PAGE_SIZE)))
+    }
+
+    fn next_cursor(count: u32, cursor: u32, end: u32) -> Option<u32> {
+        if cursor < end && end < count {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
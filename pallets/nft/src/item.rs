@@ -1,37 +1,114 @@
 use frame_support::{
     dispatch::{result::Result, DispatchError, DispatchResult},
-    traits::Get,
+    ensure,
+    StorageDoubleMap,
 };
+use sp_runtime::{traits::Zero, Permill};
+use sp_std::{vec, vec::Vec};
 
-use sp_std::vec::Vec;
+use crate::{with_transaction_result, Balances, Error, Module, Role, Tokens, Trait};
 
 pub trait Domain<AccountId> {
     type DomainId;
     type ItemId;
+    type Balance;
     type MetaData;
 
     /// Create a domain which is owned by this account
-    fn create_domain(owner: &AccountId, name: &str) -> Result<Self::DomainId, DispatchError>;
-    /// Mint items for this domain, minting 1 would make the item unique
-    fn mint_item(domain_id: &Self::DomainId, amount: u64, data: Option<Self::MetaData>) -> Result<Self::ItemId, DispatchError>;
-    /// Total minted for a item
-    fn total_items_for_domain(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> u128;
+    fn create_domain(owner: &AccountId, symbol: Vec<u8>, name: Vec<u8>) -> Result<Self::DomainId, DispatchError>;
+    /// Mint items for this domain to the creator, minting 1 would make the item unique
+    fn mint_item(domain_id: &Self::DomainId, creator: &AccountId, amount: Self::Balance, data: Self::MetaData) -> Result<Self::ItemId, DispatchError>;
+    /// Supply of an item in circulation, burned items excluded
+    fn total_items_for_domain(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> Self::Balance;
     /// Total burned for a item
-    fn burnt_items_for_domain(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> u128;
+    fn burnt_items_for_domain(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> Self::Balance;
     /// Balance of item for user
-    fn balance_for_user(domain_id: &Self::DomainId, item_id: &Self::ItemId, owner: &AccountId) -> u128;
-    /// Owner of item
-    fn owner_of_item(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> AccountId;
+    fn balance_for_user(domain_id: &Self::DomainId, item_id: &Self::ItemId, owner: &AccountId) -> Self::Balance;
+    /// Creator of item
+    fn owner_of_item(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> Option<AccountId>;
     /// Transfer item from owner to new owner
-    fn transfer_item(domain_id: &Self::DomainId, item_id: &Self::ItemId, amount: u64, to: &AccountId) -> Result<(), DispatchError>;
-    /// Transfer multiple item
-    fn transfer_multiple_items(domain_id: &Self::DomainId, item_ids: Vec<Self::ItemId>, amount: Vec<u64>, to: Vec<AccountId>) -> Result<(), DispatchError>;
+    fn transfer_item(domain_id: &Self::DomainId, item_id: &Self::ItemId, from: &AccountId, to: &AccountId, amount: Self::Balance) -> DispatchResult;
+    /// Transfer multiple items from owner to new owners, all or nothing
+    fn transfer_multiple_items(domain_id: &Self::DomainId, from: &AccountId, item_ids: Vec<Self::ItemId>, amounts: Vec<Self::Balance>, to: Vec<AccountId>) -> DispatchResult;
     /// Approval account for item
-    fn approval_for_item(domain_id: &Self::DomainId, item_id: &Self::ItemId, approved: &AccountId) -> Result<(), DispatchError>;
+    fn approval_for_item(domain_id: &Self::DomainId, item_id: &Self::ItemId, owner: &AccountId, approved: &AccountId) -> DispatchResult;
     /// Approval account for multiple item
-    fn approval_multiple_for_items(domain_id: &Self::DomainId, item_ids: Vec<Self::ItemId>, approved: &AccountId) -> Result<(), DispatchError>;
+    fn approval_multiple_for_items(domain_id: &Self::DomainId, item_ids: Vec<Self::ItemId>, owner: &AccountId, approved: &AccountId) -> DispatchResult;
     /// Burn items
-    fn burn_items(domain_id: &Self::DomainId, item_id: &Self::ItemId, amount: u64) -> Result<(), DispatchError>;
+    fn burn_items(domain_id: &Self::DomainId, item_id: &Self::ItemId, from: &AccountId, amount: Self::Balance) -> DispatchResult;
     /// Data for item
     fn data_for_item(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> Option<Self::MetaData>;
-}
\ No newline at end of file
+}
+
+impl<T: Trait> Domain<T::AccountId> for Module<T> {
+    type DomainId = T::DomainId;
+    type ItemId = T::TokenId;
+    type Balance = T::Balance;
+    type MetaData = Vec<u8>;
+
+    fn create_domain(owner: &T::AccountId, symbol: Vec<u8>, name: Vec<u8>) -> Result<T::DomainId, DispatchError> {
+        Self::do_create_domain(owner.clone(), symbol, name)
+    }
+
+    fn mint_item(domain_id: &T::DomainId, creator: &T::AccountId, amount: T::Balance, data: Vec<u8>) -> Result<T::TokenId, DispatchError> {
+        Self::ensure_role(*domain_id, creator, Role::Minter)?;
+        Self::do_create_token(*domain_id, creator.clone(), amount, data, Permill::from_percent(0), None)
+    }
+
+    fn total_items_for_domain(domain_id: &T::DomainId, item_id: &T::TokenId) -> T::Balance {
+        Tokens::<T>::get(domain_id, item_id).total_supply
+    }
+
+    fn burnt_items_for_domain(domain_id: &T::DomainId, item_id: &T::TokenId) -> T::Balance {
+        Tokens::<T>::get(domain_id, item_id).burned
+    }
+
+    fn balance_for_user(domain_id: &T::DomainId, item_id: &T::TokenId, owner: &T::AccountId) -> T::Balance {
+        Balances::<T>::get(owner, (*domain_id, *item_id))
+    }
+
+    fn owner_of_item(domain_id: &T::DomainId, item_id: &T::TokenId) -> Option<T::AccountId> {
+        if Tokens::<T>::contains_key(domain_id, item_id) {
+            Some(Tokens::<T>::get(domain_id, item_id).creator)
+        } else {
+            None
+        }
+    }
+
+    fn transfer_item(domain_id: &T::DomainId, item_id: &T::TokenId, from: &T::AccountId, to: &T::AccountId, amount: T::Balance) -> DispatchResult {
+        ensure!(amount > Zero::zero(), Error::<T>::InvalidQuantityToTransfer);
+        Self::do_transfer(from.clone(), to.clone(), *domain_id, *item_id, amount)
+    }
+
+    fn transfer_multiple_items(domain_id: &T::DomainId, from: &T::AccountId, item_ids: Vec<T::TokenId>, amounts: Vec<T::Balance>, to: Vec<T::AccountId>) -> DispatchResult {
+        ensure!(!item_ids.is_empty(), Error::<T>::EmptyBatch);
+        ensure!(item_ids.len() == amounts.len() && item_ids.len() == to.len(), Error::<T>::BatchLengthMismatch);
+        with_transaction_result(|| {
+            for ((item_id, amount), to) in item_ids.iter().zip(amounts.iter()).zip(to.iter()) {
+                Self::transfer_item(domain_id, item_id, from, to, *amount)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn approval_for_item(domain_id: &T::DomainId, item_id: &T::TokenId, owner: &T::AccountId, approved: &T::AccountId) -> DispatchResult {
+        Self::do_set_approval_for_tokens(owner.clone(), approved.clone(), *domain_id, vec![*item_id], true)
+    }
+
+    fn approval_multiple_for_items(domain_id: &T::DomainId, item_ids: Vec<T::TokenId>, owner: &T::AccountId, approved: &T::AccountId) -> DispatchResult {
+        Self::do_set_approval_for_tokens(owner.clone(), approved.clone(), *domain_id, item_ids, true)
+    }
+
+    fn burn_items(domain_id: &T::DomainId, item_id: &T::TokenId, from: &T::AccountId, amount: T::Balance) -> DispatchResult {
+        ensure!(amount > Zero::zero(), Error::<T>::InvalidQuantityToBurn);
+        Self::do_burn(from.clone(), *domain_id, *item_id, amount)
+    }
+
+    fn data_for_item(domain_id: &T::DomainId, item_id: &T::TokenId) -> Option<Vec<u8>> {
+        if Tokens::<T>::contains_key(domain_id, item_id) {
+            Some(Tokens::<T>::get(domain_id, item_id).base_uri)
+        } else {
+            None
+        }
+    }
+}
@@ -1,11 +1,35 @@
 #![cfg_attr(not(feature = "std"), no_std)]
-use frame_support::{Parameter, decl_error, decl_event, decl_module, decl_storage, dispatch::{DispatchError, DispatchResult}, ensure, traits::{Get}};
+// decl_module! recurses once per dispatchable
+#![recursion_limit = "256"]
+// Expansions of decl_event! and the codec 1.3 derives, not code in this crate
+#![allow(clippy::unused_unit, unexpected_cfgs)]
+use frame_support::{Parameter, decl_error, decl_event, decl_module, decl_storage, dispatch::{DispatchError, DispatchResult}, ensure, IterableStorageDoubleMap, storage::{with_transaction, TransactionOutcome}, traits::{BalanceStatus, Currency, Get, ReservableCurrency}, weights::Weight};
 use frame_system::ensure_signed;
-use sp_runtime::traits::{AtLeast32BitUnsigned, Zero, One, MaybeSerializeDeserialize, Member, CheckedAdd, CheckedSub};
+use sp_runtime::{Permill, RuntimeDebug, traits::{AtLeast32BitUnsigned, Zero, One, MaybeSerializeDeserialize, Member, CheckedAdd, CheckedSub, Saturating}};
 use sp_std::result::Result;
 use sp_std::fmt::Debug;
 use sp_std::vec::Vec;
 use codec::{Codec, Encode, Decode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use ownable::Ownable;
+pub mod attribute;
+pub mod auction;
+pub mod deposit;
+pub mod destroy;
+pub mod index;
+pub mod item;
+pub mod market;
+pub mod metadata;
+pub mod migrations;
+pub mod ownable;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[cfg(test)]
 mod mock;
 
@@ -17,47 +41,360 @@ pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 	type DomainId: Parameter + Member + AtLeast32BitUnsigned + Codec + Default + Copy + MaybeSerializeDeserialize + Debug + CheckedAdd;
 	type TokenId: Parameter + Member + AtLeast32BitUnsigned + Codec + Default + Copy + MaybeSerializeDeserialize + Debug + CheckedAdd;
+	type Currency: ReservableCurrency<Self::AccountId>;
+	type AuctionExtendPeriod: Get<Self::BlockNumber>;
+	type MaxAuctionsEndingPerBlock: Get<u32>;
+	type KeyLimit: Get<u32>;
+	type ValueLimit: Get<u32>;
+	type AttributeDepositBase: Get<BalanceOf<Self>>;
+	type DomainDepositBase: Get<BalanceOf<Self>>;
+	type TokenDepositBase: Get<BalanceOf<Self>>;
+	type DepositPerByte: Get<BalanceOf<Self>>;
+	type WeightInfo: WeightInfo;
+}
+
+pub type BidId = u64;
+
+pub type AuctionId = u64;
+
+pub type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// One transfer of a batch as (domain, token, recipient, quantity)
+pub type BatchTransfer<T> = (<T as Trait>::DomainId, <T as Trait>::TokenId, <T as frame_system::Trait>::AccountId, <T as Trait>::Balance);
+
+/// An allowance as stored for a spender
+pub type AllowanceOf<T> = Allowance<<T as Trait>::Balance, <T as frame_system::Trait>::BlockNumber>;
+
+/// A bid as stored on the book
+pub type BidOf<T> = Bid<<T as frame_system::Trait>::AccountId, <T as Trait>::Balance, BalanceOf<T>>;
+
+/// An english auction as stored
+pub type AuctionOf<T> = Auction<<T as frame_system::Trait>::AccountId, <T as Trait>::DomainId, <T as Trait>::TokenId, <T as Trait>::Balance, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+/// A dutch auction as stored
+pub type DutchAuctionOf<T> = DutchAuction<<T as frame_system::Trait>::AccountId, <T as Trait>::DomainId, <T as Trait>::TokenId, <T as Trait>::Balance, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+/// `sp_core::bytes` for optional fields, `None` is `null`
+#[cfg(feature = "std")]
+mod option_bytes {
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+	use sp_core::Bytes;
+
+	pub fn serialize<S: Serializer>(bytes: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+		bytes.as_ref().map(|bytes| Bytes(bytes.clone())).serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error> {
+		Ok(Option::<Bytes>::deserialize(deserializer)?.map(|bytes| bytes.0))
+	}
 }
 
 #[derive(Encode, Decode, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct Domain<TokenId, AccountId> {
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
 	symbol: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
 	name: Vec<u8>,
 	next_token_id: TokenId,
 	owner: AccountId,
-	
+	#[cfg_attr(feature = "std", serde(with = "option_bytes"))]
+	uri_template: Option<Vec<u8>>,
+	/// Set once teardown has started, after which nothing new can be added
+	destroying: bool,
+}
+
+/// Upper bounds on what a single `destroy_domain` or `destroy_token` call removes. Approvals
+/// covers operators, roles, token approvals, allowances, freezes and open bids.
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct DestroyWitness {
+	pub tokens: u32,
+	pub approvals: u32,
+	pub attributes: u32,
+}
+
+/// Layout of the pallet's storage, bumped whenever a migration has to run
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	#[default]
+	V1_0_0,
+	/// Adds royalties, supply caps, burned supply, transfer policy and URIs to tokens
+	/// and URI templates and teardown state to domains
+	V2_0_0,
+	/// Adds the holder and owned token indexes
+	V3_0_0,
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Role {
+	/// Can do anything the owner can except grant or revoke admins
+	Admin,
+	Minter,
+	Burner,
+	MetadataEditor,
+}
+
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TransferPolicy {
+	#[default]
+	Transferable,
+	/// Bound to the holder once minted
+	Soulbound,
+	/// Only the domain owner can move them
+	DomainOwnerOnly,
 }
 
 #[derive(Encode, Decode, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct Token<AccountId, Balance> {
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
 	base_uri: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(with = "option_bytes"))]
+	uri: Option<Vec<u8>>,
 	total_supply: Balance,
+	burned: Balance,
+	max_supply: Option<Balance>,
 	creator: AccountId,
+	royalty: Permill,
+	transfer_policy: TransferPolicy,
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq)]
+pub struct Listing<Balance, Price> {
+	quantity: Balance,
+	price: Price,
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq)]
+pub struct Bid<AccountId, Balance, Price> {
+	bidder: AccountId,
+	seller: AccountId,
+	quantity: Balance,
+	price: Price,
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq)]
+pub struct Auction<AccountId, DomainId, TokenId, Balance, Price, BlockNumber> {
+	seller: AccountId,
+	domain_id: DomainId,
+	token_id: TokenId,
+	quantity: Balance,
+	reserve_price: Price,
+	min_increment: Price,
+	end: BlockNumber,
+	highest_bid: Option<(AccountId, Price)>,
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq)]
+pub struct DutchAuction<AccountId, DomainId, TokenId, Balance, Price, BlockNumber> {
+	seller: AccountId,
+	domain_id: DomainId,
+	token_id: TokenId,
+	quantity: Balance,
+	start_price: Price,
+	floor_price: Price,
+	decay_per_block: Price,
+	start: BlockNumber,
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq)]
+pub struct Allowance<Balance, BlockNumber> {
+	amount: Balance,
+	expires_at: Option<BlockNumber>,
 }
 
+// Stays on the decl_* macros until the workspace moves to Substrate 3.0, the first release
+// with `#[frame_support::pallet]`. The port has to keep the `NFT` storage prefix and the order
+// of the calls in decl_module! so existing chains don't need a migration.
 decl_storage! {
 	trait Store for Module<T: Trait> as NFT {
 
+		/// Id the next domain gets
 		pub NextDomainId get(fn next_domain_id): T::DomainId;
+
+		/// Chains started before this was added are on `V1_0_0`, new ones start on the latest
+		StorageVersion build(|_: &GenesisConfig<T>| Releases::V3_0_0): Releases;
+
+		/// Where the V3 index build has got to in `Balances`, set while it runs
+		IndexCursor: Option<Vec<u8>>;
 		
+		/// Numbers accounts have set against tokens with `add_number`
 		pub Numbers get(fn numbers):
 			double_map
 			hasher(blake2_128_concat) T::AccountId,
 			hasher(blake2_128_concat) (T::DomainId, T::TokenId) => T::Balance;
 
+		/// Domains by id
 		pub Domains get(fn domains): 
 			map 
 			hasher(blake2_128_concat) T::DomainId => Domain<T::TokenId, T::AccountId>; 
+
+		/// Accounts offered ownership of a domain that haven't accepted yet
+		pub PendingDomainOwners get(fn pending_domain_owners):
+			map
+			hasher(blake2_128_concat) T::DomainId => Option<T::AccountId>;
+
+		/// Key value attributes of a domain (`None`) or one of its tokens, with the deposit held for each
+		pub Attributes get(fn attributes):
+			double_map
+			hasher(blake2_128_concat) (T::DomainId, Option<T::TokenId>),
+			hasher(blake2_128_concat) Vec<u8> => Option<(Vec<u8>, BalanceOf<T>)>;
+
+		/// Everything the domain owner has reserved for the domain, its tokens and attributes
+		pub DomainDeposits get(fn domain_deposits):
+			map
+			hasher(blake2_128_concat) T::DomainId => BalanceOf<T>;
+
+		/// The part of the domain deposit held for the domain itself (`None`) or one of its tokens
+		pub Deposits get(fn deposits):
+			double_map
+			hasher(blake2_128_concat) T::DomainId,
+			hasher(blake2_128_concat) Option<T::TokenId> => BalanceOf<T>;
+
+		/// Roles the domain owner or its admins have granted
+		pub Roles get(fn roles):
+			double_map
+			hasher(blake2_128_concat) T::DomainId,
+			hasher(blake2_128_concat) (T::AccountId, Role) => bool;
 		
+		/// Tokens by domain and id
 		pub Tokens get(fn tokens):
 			double_map 
 			hasher(blake2_128_concat) T::DomainId,
 			hasher(blake2_128_concat) T::TokenId => Token<T::AccountId, T::Balance>;
 
+		/// What each account holds of each token
 		pub Balances get(fn balances):
 			double_map 
 			hasher(blake2_128_concat) T::AccountId,
 			hasher(blake2_128_concat) (T::DomainId, T::TokenId) => T::Balance;
+
+		/// Accounts with a non-zero balance of a token, by position
+		pub Holders get(fn holders):
+			double_map
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId),
+			hasher(blake2_128_concat) u32 => Option<T::AccountId>;
+
+		/// Where each holder sits in `Holders`
+		pub HolderPositions get(fn holder_positions):
+			double_map
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId),
+			hasher(blake2_128_concat) T::AccountId => Option<u32>;
+
+		/// Length of `Holders` for each token
+		pub HolderCount get(fn holder_count):
+			map
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId) => u32;
+
+		/// Tokens an account has a non-zero balance of, by position
+		pub OwnedTokens get(fn owned_tokens):
+			double_map
+			hasher(blake2_128_concat) T::AccountId,
+			hasher(blake2_128_concat) u32 => Option<(T::DomainId, T::TokenId)>;
+
+		/// Where each token sits in `OwnedTokens`
+		pub OwnedTokenPositions get(fn owned_token_positions):
+			double_map
+			hasher(blake2_128_concat) T::AccountId,
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId) => Option<u32>;
+
+		/// Length of `OwnedTokens` for each account
+		pub OwnedTokenCount get(fn owned_token_count):
+			map
+			hasher(blake2_128_concat) T::AccountId => u32;
+
+		/// (holder, operator) pairs where the operator can move any of the holder's tokens in the domain
+		pub Operators get(fn operators):
+			double_map
+			hasher(blake2_128_concat) T::DomainId,
+			hasher(blake2_128_concat) (T::AccountId, T::AccountId) => bool;
+
+		/// (holder, operator) pairs where the operator can move any of the holder's balance of the token
+		pub Approvals get(fn approvals):
+			double_map
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId),
+			hasher(blake2_128_concat) (T::AccountId, T::AccountId) => bool;
+
+		/// (holder, spender) pairs where the spender can move up to an amount of the token
+		pub Allowances get(fn allowances):
+			double_map
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId),
+			hasher(blake2_128_concat) (T::AccountId, T::AccountId) => Option<AllowanceOf<T>>;
+
+		/// Fixed price listings by token and seller
+		pub Listings get(fn listings):
+			double_map
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId),
+			hasher(blake2_128_concat) T::AccountId => Option<Listing<T::Balance, BalanceOf<T>>>;
+
+		/// The part of each balance held by listings and auctions
+		pub Locked get(fn locked):
+			double_map
+			hasher(blake2_128_concat) T::AccountId,
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId) => T::Balance;
+
+		/// Holders the domain admins have barred from moving the token
+		pub Frozen get(fn frozen):
+			double_map
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId),
+			hasher(blake2_128_concat) T::AccountId => bool;
+
+		/// Id the next bid gets
+		pub NextBidId get(fn next_bid_id): BidId;
+
+		/// Open bids by token, with the price reserved from the bidder
+		pub Bids get(fn bids):
+			double_map
+			hasher(blake2_128_concat) (T::DomainId, T::TokenId),
+			hasher(blake2_128_concat) BidId => Option<BidOf<T>>;
+
+		/// Id the next auction gets, shared by english and dutch auctions
+		pub NextAuctionId get(fn next_auction_id): AuctionId;
+
+		/// Running english auctions
+		pub Auctions get(fn auctions):
+			map
+			hasher(blake2_128_concat) AuctionId => Option<AuctionOf<T>>;
+
+		/// English auctions by the block they end in, settled in `on_initialize`
+		pub AuctionEndings get(fn auction_endings):
+			map
+			hasher(blake2_128_concat) T::BlockNumber => Vec<AuctionId>;
+
+		/// Running dutch auctions
+		pub DutchAuctions get(fn dutch_auctions):
+			map
+			hasher(blake2_128_concat) AuctionId => Option<DutchAuctionOf<T>>;
+	}
+	add_extra_genesis {
+		/// Domains as (owner, symbol, name), numbered in order from zero
+		config(domains): Vec<(T::AccountId, Vec<u8>, Vec<u8>)>;
+		/// Tokens as (domain, creator, supply, base_uri), the supply is minted to the creator
+		config(tokens): Vec<(T::DomainId, T::AccountId, T::Balance, Vec<u8>)>;
+		/// Balances as (holder, domain, token, quantity), moved out of the creator's supply
+		config(balances): Vec<(T::AccountId, T::DomainId, T::TokenId, T::Balance)>;
+		build(|config: &GenesisConfig<T>| {
+			// Deposits are reserved as usual, so owners must be endowed by the balances pallet.
+			// Genesis storage can't open transactions, but any failure aborts the build anyway.
+			for (owner, symbol, name) in config.domains.iter() {
+				Module::<T>::insert_domain(owner.clone(), symbol.clone(), name.clone())
+					.expect("genesis domains must have a valid symbol and name and an endowed owner");
+			}
+			for (domain_id, creator, supply, base_uri) in config.tokens.iter() {
+				Module::<T>::insert_token(*domain_id, creator.clone(), *supply, base_uri.clone(), Permill::zero(), None)
+					.expect("genesis tokens must belong to a genesis domain and have a supply and base uri");
+			}
+			for (holder, domain_id, token_id, quantity) in config.balances.iter() {
+				assert!(!quantity.is_zero(), "genesis balances must not be empty");
+				assert!(Tokens::<T>::contains_key(domain_id, token_id), "genesis balances must be of a genesis token");
+				let creator = Tokens::<T>::get(domain_id, token_id).creator;
+				Module::<T>::do_transfer(creator, holder.clone(), *domain_id, *token_id, *quantity)
+					.expect("genesis balances must not exceed the supply held by the token creator");
+			}
+		});
 	}
 }
 
@@ -65,12 +402,55 @@ decl_event!(
 	pub enum Event<T> 
 	where 
 	AccountId = <T as frame_system::Trait>::AccountId,
+	BlockNumber = <T as frame_system::Trait>::BlockNumber,
 	DomainId = <T as Trait>::DomainId,
 	TokenId = <T as Trait>::TokenId,
-	Balance = <T as Trait>::Balance {
+	Balance = <T as Trait>::Balance,
+	Price = BalanceOf<T> {
 		DomainCreated(AccountId, DomainId),
 		TokenCreated(AccountId, DomainId, TokenId, Balance),
 		TokensBurnt(AccountId, DomainId, TokenId, Balance),
+		TokensTransferred(AccountId, AccountId, DomainId, TokenId, Balance),
+		TokensBatchTransferred(AccountId, Vec<(DomainId, TokenId, AccountId, Balance)>),
+		OperatorApproved(AccountId, AccountId, DomainId),
+		OperatorRevoked(AccountId, AccountId, DomainId),
+		TokensApproved(AccountId, AccountId, DomainId, Vec<TokenId>),
+		TokenApprovalsRevoked(AccountId, AccountId, DomainId, Vec<TokenId>),
+		TokensTransferredByOperator(AccountId, AccountId, AccountId, DomainId, TokenId, Balance),
+		AllowanceSet(AccountId, AccountId, DomainId, TokenId, Balance, Option<BlockNumber>),
+		TokensListed(AccountId, DomainId, TokenId, Balance, Price),
+		ListingCancelled(AccountId, DomainId, TokenId),
+		TokensSold(AccountId, AccountId, DomainId, TokenId, Balance, Price),
+		BidPlaced(BidId, AccountId, AccountId, DomainId, TokenId, Balance, Price),
+		BidAccepted(BidId, AccountId, AccountId, DomainId, TokenId, Balance, Price),
+		BidRejected(BidId, DomainId, TokenId),
+		BidCancelled(BidId, DomainId, TokenId),
+		AuctionCreated(AuctionId, AccountId, DomainId, TokenId, Balance, Price, BlockNumber),
+		AuctionBid(AuctionId, AccountId, Price),
+		AuctionExtended(AuctionId, BlockNumber),
+		AuctionSettled(AuctionId, Option<AccountId>, Price),
+		AuctionCancelled(AuctionId),
+		DutchAuctionCreated(AuctionId, AccountId, DomainId, TokenId, Balance, Price, Price, Price),
+		DutchAuctionPurchase(AuctionId, AccountId, Balance, Price),
+		DutchAuctionClosed(AuctionId),
+		RoyaltySet(DomainId, TokenId, Permill),
+		RoyaltyPaid(AccountId, DomainId, TokenId, Price),
+		DomainOwnershipProposed(DomainId, AccountId, AccountId),
+		DomainOwnershipTransferred(DomainId, AccountId, AccountId),
+		DomainOwnershipTransferCancelled(DomainId),
+		RoleGranted(DomainId, AccountId, Role),
+		RoleRevoked(DomainId, AccountId, Role),
+		TokensMinted(AccountId, DomainId, TokenId, Balance),
+		MaxSupplySet(DomainId, TokenId, Balance),
+		TransferPolicySet(DomainId, TokenId, TransferPolicy),
+		AccountFrozen(AccountId, DomainId, TokenId),
+		AccountThawed(AccountId, DomainId, TokenId),
+		AttributeSet(DomainId, Option<TokenId>, Vec<u8>, Vec<u8>),
+		AttributeCleared(DomainId, Option<TokenId>, Vec<u8>),
+		TokenUriSet(DomainId, TokenId, Option<Vec<u8>>),
+		UriTemplateSet(DomainId, Option<Vec<u8>>),
+		TokenDestroyed(DomainId, TokenId),
+		DomainDestroyed(DomainId),
 	}
 );
 
@@ -87,6 +467,55 @@ decl_error! {
 		InvalidQuantityToBurn,
 		InvalidBaseUri,
 		BalanceOverflow,
+		InvalidQuantityToTransfer,
+		InsufficientBalance,
+		EmptyBatch,
+		BatchLengthMismatch,
+		InvalidOperator,
+		NotApproved,
+		InvalidExpiry,
+		AllowanceExpired,
+		InsufficientAllowance,
+		AllowanceOverflow,
+		InvalidQuantityToList,
+		InvalidPrice,
+		PriceOverflow,
+		ListingNotFound,
+		InsufficientListingQuantity,
+		CannotBuyOwnListing,
+		TokensLocked,
+		BidIdOverflow,
+		BidNotFound,
+		NotBidSeller,
+		NotBidder,
+		AuctionIdOverflow,
+		AuctionNotFound,
+		AuctionEnded,
+		InvalidAuctionEnd,
+		TooManyAuctionsEnding,
+		CannotBidOnOwnAuction,
+		BidTooLow,
+		NotAuctionSeller,
+		AuctionHasBids,
+		PriceAboveLimit,
+		InvalidToken,
+		NotTokenCreator,
+		RoyaltyIncrease,
+		NotPendingOwner,
+		NoPendingOwner,
+		MissingRole,
+		InvalidQuantityToMint,
+		MaxSupplyExceeded,
+		InvalidMaxSupply,
+		TokenNotTransferable,
+		TransferRestricted,
+		AccountFrozen,
+		KeyTooLong,
+		ValueTooLong,
+		AttributeNotFound,
+		TokenNotEmpty,
+		DomainDestroying,
+		BadWitness,
 	}
 }
 
@@ -95,82 +524,705 @@ decl_module! {
 		type Error = Error<T>;
 		fn deposit_event() = default;
 
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		const AuctionExtendPeriod: T::BlockNumber = T::AuctionExtendPeriod::get();
+		const MaxAuctionsEndingPerBlock: u32 = T::MaxAuctionsEndingPerBlock::get();
+		const KeyLimit: u32 = T::KeyLimit::get();
+		const ValueLimit: u32 = T::ValueLimit::get();
+		const AttributeDepositBase: BalanceOf<T> = T::AttributeDepositBase::get();
+		const DomainDepositBase: BalanceOf<T> = T::DomainDepositBase::get();
+		const TokenDepositBase: BalanceOf<T> = T::TokenDepositBase::get();
+		const DepositPerByte: BalanceOf<T> = T::DepositPerByte::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::settle_auctions(now).saturating_add(migrations::v3::step::<T>(migrations::v3::INDEX_PER_BLOCK))
+		}
+
+		#[weight = T::WeightInfo::add_number()]
 		pub fn add_number(origin, domain_id: T::DomainId, token_id: T::TokenId, number: T::Balance) {
 			let owner = ensure_signed(origin)?;
 			Numbers::<T>::insert(owner.clone(), (domain_id, token_id), number);
 		}
 
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		#[weight = T::WeightInfo::create_domain()]
 		pub fn create_domain(origin, symbol: Vec<u8>, name: Vec<u8>) {
-			ensure!(symbol.len() > 2, Error::<T>::InvalidSymbol);
-			ensure!(name.len() > 3, Error::<T>::InvalidName);
 			let owner = ensure_signed(origin)?;
-			
-			let domain = Domain {
-				symbol,
-				name,
-				next_token_id: Zero::zero(),
-				owner: owner.clone()
-			};
-
-			let next = Self::get_next_domain_id()?;
-			Domains::<T>::insert(next, domain);
-			Self::deposit_event(RawEvent::DomainCreated(owner, next));
-		}
-
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]		
-		pub fn create_token(origin, domain_id: T::DomainId, creator: T::AccountId, total_supply: T::Balance, base_uri: Vec<u8>) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			ensure!(total_supply > Zero::zero(), Error::<T>::InvalidTotalSupply);
-			ensure!(base_uri.len() > 3, Error::<T>::InvalidBaseUri);			
-
-			Domains::<T>::try_mutate(domain_id, |domain| {
-				ensure!(domain.owner == who, Error::<T>::NotDomainOwner);			
-				let token = Token {
-					base_uri,
-					total_supply,
-					creator: creator.clone(),
-				};
-				let next_token_id = domain.next_token_id.checked_add(&One::one()).ok_or(Error::<T>::TokenIdOverflow)?;
-				domain.next_token_id = next_token_id;
-				Tokens::<T>::insert(domain_id, next_token_id, token);
-				Self::mint(creator.clone(), domain_id, next_token_id, total_supply)?;
-				Self::deposit_event(RawEvent::TokenCreated(creator, domain_id, next_token_id, total_supply));
-				Ok(())
-			})
+			Self::do_create_domain(owner, symbol, name)?;
+		}
+
+		#[weight = T::WeightInfo::create_token()]
+		pub fn create_token(origin, domain_id: T::DomainId, creator: T::AccountId, total_supply: T::Balance, base_uri: Vec<u8>, royalty: Permill, max_supply: Option<T::Balance>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::Minter)?;
+			Self::do_create_token(domain_id, creator, total_supply, base_uri, royalty, max_supply)?;
+			Ok(())
 		}
 
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]		
+		#[weight = T::WeightInfo::burn_tokens()]
 		pub fn burn_tokens(origin, domain_id: T::DomainId, token_id: T::TokenId, victim: T::AccountId, quantity: T::Balance) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToBurn);	
-			let domain = Domains::<T>::get(domain_id);
-			ensure!(domain.owner == who, Error::<T>::NotDomainOwner);			
-			Self::burn(victim.clone(), domain_id, token_id, quantity)?;
-			Self::deposit_event(RawEvent::TokensBurnt(victim, domain_id, token_id, quantity));
+			Self::ensure_role(domain_id, &who, Role::Burner)?;
+			Self::do_burn(victim, domain_id, token_id, quantity)
+		}
+
+		#[weight = T::WeightInfo::transfer()]
+		pub fn transfer(origin, domain_id: T::DomainId, token_id: T::TokenId, to: T::AccountId, quantity: T::Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToTransfer);
+			Self::do_transfer(who.clone(), to.clone(), domain_id, token_id, quantity)?;
+			Self::deposit_event(RawEvent::TokensTransferred(who, to, domain_id, token_id, quantity));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::transfer_batch(transfers.len() as u32)]
+		pub fn transfer_batch(origin, transfers: Vec<BatchTransfer<T>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!transfers.is_empty(), Error::<T>::EmptyBatch);
+			Self::do_transfer_batch(&who, &transfers)?;
+			Self::deposit_event(RawEvent::TokensBatchTransferred(who, transfers));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::set_approval_for_all()]
+		pub fn set_approval_for_all(origin, domain_id: T::DomainId, operator: T::AccountId, approved: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != operator, Error::<T>::InvalidOperator);
+			Self::ensure_live_domain(domain_id)?;
+			if approved {
+				Operators::<T>::insert(domain_id, (who.clone(), operator.clone()), true);
+				Self::deposit_event(RawEvent::OperatorApproved(who, operator, domain_id));
+			} else {
+				Operators::<T>::remove(domain_id, (who.clone(), operator.clone()));
+				Self::deposit_event(RawEvent::OperatorRevoked(who, operator, domain_id));
+			}
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::set_approval_for_tokens(token_ids.len() as u32)]
+		pub fn set_approval_for_tokens(origin, domain_id: T::DomainId, token_ids: Vec<T::TokenId>, operator: T::AccountId, approved: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_approval_for_tokens(who, operator, domain_id, token_ids, approved)
+		}
+
+		#[weight = T::WeightInfo::transfer_from()]
+		pub fn transfer_from(origin, domain_id: T::DomainId, token_id: T::TokenId, from: T::AccountId, to: T::AccountId, quantity: T::Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToTransfer);
+			with_transaction_result(|| {
+				if !Self::is_approved(&from, &who, domain_id, token_id) {
+					Self::spend_allowance(&from, &who, domain_id, token_id, quantity)?;
+				}
+				Self::do_transfer_as(&who, from.clone(), to.clone(), domain_id, token_id, quantity)
+			})?;
+			Self::deposit_event(RawEvent::TokensTransferredByOperator(who, from, to, domain_id, token_id, quantity));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::approve()]
+		pub fn approve(origin, domain_id: T::DomainId, token_id: T::TokenId, spender: T::AccountId, amount: T::Balance, expires_at: Option<T::BlockNumber>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != spender, Error::<T>::InvalidOperator);
+			Self::ensure_live_domain(domain_id)?;
+			ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+			if let Some(expires_at) = expires_at {
+				ensure!(expires_at > frame_system::Module::<T>::block_number(), Error::<T>::InvalidExpiry);
+			}
+			Self::set_allowance(&who, &spender, domain_id, token_id, Allowance { amount, expires_at });
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::increase_allowance()]
+		pub fn increase_allowance(origin, domain_id: T::DomainId, token_id: T::TokenId, spender: T::AccountId, amount: T::Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(who != spender, Error::<T>::InvalidOperator);
+			Self::ensure_live_domain(domain_id)?;
+			ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+			let mut allowance = Self::current_allowance(&who, &spender, domain_id, token_id)?.unwrap_or_default();
+			allowance.amount = allowance.amount.checked_add(&amount).ok_or(Error::<T>::AllowanceOverflow)?;
+			Self::set_allowance(&who, &spender, domain_id, token_id, allowance);
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::decrease_allowance()]
+		pub fn decrease_allowance(origin, domain_id: T::DomainId, token_id: T::TokenId, spender: T::AccountId, amount: T::Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// Expired allowances can still be reduced, so their owner can clear them
+			let mut allowance = Allowances::<T>::get((domain_id, token_id), (who.clone(), spender.clone())).ok_or(Error::<T>::InsufficientAllowance)?;
+			allowance.amount = allowance.amount.checked_sub(&amount).ok_or(Error::<T>::InsufficientAllowance)?;
+			Self::set_allowance(&who, &spender, domain_id, token_id, allowance);
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::list_tokens()]
+		pub fn list_tokens(origin, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance, price: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_list(who, domain_id, token_id, quantity, price)
+		}
+
+		#[weight = T::WeightInfo::cancel_listing()]
+		pub fn cancel_listing(origin, domain_id: T::DomainId, token_id: T::TokenId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_cancel_listing(who, domain_id, token_id)
+		}
+
+		#[weight = T::WeightInfo::buy_tokens()]
+		pub fn buy_tokens(origin, domain_id: T::DomainId, token_id: T::TokenId, seller: T::AccountId, quantity: T::Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| Self::do_buy(who, domain_id, token_id, seller, quantity))
+		}
+
+		#[weight = T::WeightInfo::place_bid()]
+		pub fn place_bid(origin, domain_id: T::DomainId, token_id: T::TokenId, seller: T::AccountId, quantity: T::Balance, price: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| Self::do_place_bid(who, domain_id, token_id, seller, quantity, price))?;
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::accept_bid()]
+		pub fn accept_bid(origin, domain_id: T::DomainId, token_id: T::TokenId, bid_id: BidId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| Self::do_settle_bid(who, domain_id, token_id, bid_id, true))
+		}
+
+		#[weight = T::WeightInfo::reject_bid()]
+		pub fn reject_bid(origin, domain_id: T::DomainId, token_id: T::TokenId, bid_id: BidId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_settle_bid(who, domain_id, token_id, bid_id, false)
+		}
+
+		#[weight = T::WeightInfo::cancel_bid()]
+		pub fn cancel_bid(origin, domain_id: T::DomainId, token_id: T::TokenId, bid_id: BidId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let bid = Bids::<T>::get((domain_id, token_id), bid_id).ok_or(Error::<T>::BidNotFound)?;
+			ensure!(bid.bidder == who, Error::<T>::NotBidder);
+			T::Currency::unreserve(&who, Self::price_for(bid.quantity, bid.price)?);
+			Bids::<T>::remove((domain_id, token_id), bid_id);
+			Self::deposit_event(RawEvent::BidCancelled(bid_id, domain_id, token_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::create_auction()]
+		pub fn create_auction(origin, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance, reserve_price: BalanceOf<T>, min_increment: BalanceOf<T>, end: T::BlockNumber) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_create_auction(who, domain_id, token_id, quantity, reserve_price, min_increment, end)?;
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::bid_on_auction()]
+		pub fn bid_on_auction(origin, auction_id: AuctionId, amount: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| Self::do_bid_on_auction(who, auction_id, amount))
+		}
+
+		#[weight = T::WeightInfo::cancel_auction()]
+		pub fn cancel_auction(origin, auction_id: AuctionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_cancel_auction(who, auction_id)
+		}
+
+		#[weight = T::WeightInfo::create_dutch_auction()]
+		pub fn create_dutch_auction(origin, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance, start_price: BalanceOf<T>, floor_price: BalanceOf<T>, decay_per_block: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Domains::<T>::get(domain_id).owner == who, Error::<T>::NotDomainOwner);
+			Self::do_create_dutch_auction(who, domain_id, token_id, quantity, start_price, floor_price, decay_per_block)?;
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::buy_from_dutch_auction()]
+		pub fn buy_from_dutch_auction(origin, auction_id: AuctionId, quantity: T::Balance, max_price: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| Self::do_buy_from_dutch_auction(who, auction_id, quantity, max_price))
+		}
+
+		#[weight = T::WeightInfo::close_dutch_auction()]
+		pub fn close_dutch_auction(origin, auction_id: AuctionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_close_dutch_auction(who, auction_id)
+		}
+
+		#[weight = T::WeightInfo::set_royalty()]
+		pub fn set_royalty(origin, domain_id: T::DomainId, token_id: T::TokenId, royalty: Permill) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+			Tokens::<T>::try_mutate(domain_id, token_id, |token| -> DispatchResult {
+				ensure!(token.creator == who, Error::<T>::NotTokenCreator);
+				ensure!(royalty <= token.royalty, Error::<T>::RoyaltyIncrease);
+				token.royalty = royalty;
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::RoyaltySet(domain_id, token_id, royalty));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::transfer_domain_ownership()]
+		pub fn transfer_domain_ownership(origin, domain_id: T::DomainId, new_owner: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::owner(&domain_id) == Some(who.clone()), Error::<T>::NotDomainOwner);
+			ensure!(new_owner != who, Error::<T>::InvalidOperator);
+			PendingDomainOwners::<T>::insert(domain_id, new_owner.clone());
+			Self::deposit_event(RawEvent::DomainOwnershipProposed(domain_id, who, new_owner));
+			Ok(())
+		}
+
+		/// Roles granted under the previous owner are dropped, the new owner starts from scratch.
+		/// `roles` must be at least the number of roles granted on the domain.
+		#[weight = T::WeightInfo::accept_domain_ownership(*roles)]
+		pub fn accept_domain_ownership(origin, domain_id: T::DomainId, roles: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(PendingDomainOwners::<T>::get(domain_id) == Some(who.clone()), Error::<T>::NotPendingOwner);
+			let granted: Vec<(T::AccountId, Role)> = Roles::<T>::iter_prefix(domain_id).map(|(key, _)| key).take((roles as usize).saturating_add(1)).collect();
+			ensure!(granted.len() as u32 <= roles, Error::<T>::BadWitness);
+			let previous_owner = with_transaction_result(|| {
+				let previous_owner = Self::set_owner(&domain_id, &who)?;
+				// The new owner takes over the deposits held for the domain
+				T::Currency::repatriate_reserved(&previous_owner, &who, DomainDeposits::<T>::get(domain_id), BalanceStatus::Reserved)?;
+				Ok(previous_owner)
+			})?;
+			for key in granted {
+				Roles::<T>::remove(domain_id, key);
+			}
+			PendingDomainOwners::<T>::remove(domain_id);
+			Self::deposit_event(RawEvent::DomainOwnershipTransferred(domain_id, previous_owner, who));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::cancel_domain_ownership_transfer()]
+		pub fn cancel_domain_ownership_transfer(origin, domain_id: T::DomainId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::owner(&domain_id) == Some(who), Error::<T>::NotDomainOwner);
+			ensure!(PendingDomainOwners::<T>::take(domain_id).is_some(), Error::<T>::NoPendingOwner);
+			Self::deposit_event(RawEvent::DomainOwnershipTransferCancelled(domain_id));
 			Ok(())
 		}
+
+		#[weight = T::WeightInfo::grant_role()]
+		pub fn grant_role(origin, domain_id: T::DomainId, account: T::AccountId, role: Role) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_can_manage_role(domain_id, &who, role)?;
+			Roles::<T>::insert(domain_id, (account.clone(), role), true);
+			Self::deposit_event(RawEvent::RoleGranted(domain_id, account, role));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::revoke_role()]
+		pub fn revoke_role(origin, domain_id: T::DomainId, account: T::AccountId, role: Role) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_can_manage_role(domain_id, &who, role)?;
+			Roles::<T>::remove(domain_id, (account.clone(), role));
+			Self::deposit_event(RawEvent::RoleRevoked(domain_id, account, role));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::mint_tokens()]
+		pub fn mint_tokens(origin, domain_id: T::DomainId, token_id: T::TokenId, to: T::AccountId, quantity: T::Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToMint);
+			Self::ensure_role(domain_id, &who, Role::Minter)?;
+			ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+			Tokens::<T>::try_mutate(domain_id, token_id, |token| -> DispatchResult {
+				let total_supply = token.total_supply.checked_add(&quantity).ok_or(Error::<T>::BalanceOverflow)?;
+				if let Some(max_supply) = token.max_supply {
+					let minted = total_supply.checked_add(&token.burned).ok_or(Error::<T>::BalanceOverflow)?;
+					ensure!(minted <= max_supply, Error::<T>::MaxSupplyExceeded);
+				}
+				token.total_supply = total_supply;
+				Ok(())
+			})?;
+			Self::mint(to.clone(), domain_id, token_id, quantity)?;
+			Self::deposit_event(RawEvent::TokensMinted(to, domain_id, token_id, quantity));
+			Ok(())
+		}
+
+		/// Lower the supply cap of a token, burned tokens count towards the cap
+		#[weight = T::WeightInfo::set_max_supply()]
+		pub fn set_max_supply(origin, domain_id: T::DomainId, token_id: T::TokenId, max_supply: T::Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::Minter)?;
+			ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+			Tokens::<T>::try_mutate(domain_id, token_id, |token| -> DispatchResult {
+				ensure!(max_supply >= token.total_supply.saturating_add(token.burned), Error::<T>::InvalidMaxSupply);
+				ensure!(token.max_supply.is_none_or(|current| max_supply <= current), Error::<T>::InvalidMaxSupply);
+				token.max_supply = Some(max_supply);
+				Ok(())
+			})?;
+			Self::deposit_event(RawEvent::MaxSupplySet(domain_id, token_id, max_supply));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::freeze_supply()]
+		pub fn freeze_supply(origin, domain_id: T::DomainId, token_id: T::TokenId) -> DispatchResult {
+			let token = Tokens::<T>::get(domain_id, token_id);
+			Self::set_max_supply(origin, domain_id, token_id, token.total_supply.saturating_add(token.burned))
+		}
+
+		#[weight = T::WeightInfo::burn_own_tokens()]
+		pub fn burn_own_tokens(origin, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToBurn);
+			ensure!(Balances::<T>::get(&who, (domain_id, token_id)) >= quantity, Error::<T>::InsufficientBalance);
+			Self::do_burn(who, domain_id, token_id, quantity)
+		}
+
+		#[weight = T::WeightInfo::set_transfer_policy()]
+		pub fn set_transfer_policy(origin, domain_id: T::DomainId, token_id: T::TokenId, transfer_policy: TransferPolicy) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::Admin)?;
+			ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+			Tokens::<T>::mutate(domain_id, token_id, |token| token.transfer_policy = transfer_policy);
+			Self::deposit_event(RawEvent::TransferPolicySet(domain_id, token_id, transfer_policy));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::freeze()]
+		pub fn freeze(origin, domain_id: T::DomainId, token_id: T::TokenId, account: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::Admin)?;
+			ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+			Frozen::<T>::insert((domain_id, token_id), &account, true);
+			Self::deposit_event(RawEvent::AccountFrozen(account, domain_id, token_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::thaw()]
+		pub fn thaw(origin, domain_id: T::DomainId, token_id: T::TokenId, account: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::Admin)?;
+			Frozen::<T>::remove((domain_id, token_id), &account);
+			Self::deposit_event(RawEvent::AccountThawed(account, domain_id, token_id));
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::set_attribute()]
+		pub fn set_attribute(origin, domain_id: T::DomainId, token_id: Option<T::TokenId>, key: Vec<u8>, value: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::MetadataEditor)?;
+			Self::do_set_attribute(domain_id, token_id, key, value)
+		}
+
+		#[weight = T::WeightInfo::clear_attribute()]
+		pub fn clear_attribute(origin, domain_id: T::DomainId, token_id: Option<T::TokenId>, key: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::MetadataEditor)?;
+			Self::do_clear_attribute(domain_id, token_id, key)
+		}
+
+		#[weight = T::WeightInfo::set_token_uri()]
+		pub fn set_token_uri(origin, domain_id: T::DomainId, token_id: T::TokenId, uri: Option<Vec<u8>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::MetadataEditor)?;
+			Self::do_set_token_uri(domain_id, token_id, uri)
+		}
+
+		#[weight = T::WeightInfo::set_uri_template()]
+		pub fn set_uri_template(origin, domain_id: T::DomainId, uri_template: Option<Vec<u8>>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::MetadataEditor)?;
+			Self::do_set_uri_template(domain_id, uri_template)
+		}
+
+		#[weight = T::WeightInfo::destroy_token(witness.approvals, witness.attributes)]
+		pub fn destroy_token(origin, domain_id: T::DomainId, token_id: T::TokenId, witness: DestroyWitness) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_role(domain_id, &who, Role::Admin)?;
+			with_transaction_result(|| Self::do_destroy_token(domain_id, token_id, witness))
+		}
+
+		#[weight = T::WeightInfo::destroy_domain(witness.tokens, witness.approvals, witness.attributes)]
+		pub fn destroy_domain(origin, domain_id: T::DomainId, witness: DestroyWitness) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			with_transaction_result(|| Self::do_destroy_domain(who, domain_id, witness))
+		}
 	}
 }
 
+/// Runs `f` in a storage transaction that is only committed if it succeeds. `#[transactional]`
+/// in frame-support 2.0.1 pastes the function body straight into the transaction closure, so it
+/// can't wrap bodies that use `?`.
+pub(crate) fn with_transaction_result<R>(f: impl FnOnce() -> Result<R, DispatchError>) -> Result<R, DispatchError> {
+	with_transaction(|| {
+		let result = f();
+		if result.is_ok() {
+			TransactionOutcome::Commit(result)
+		} else {
+			TransactionOutcome::Rollback(result)
+		}
+	})
+}
+
 impl <T: Trait> Module<T> {
+	fn do_create_domain(owner: T::AccountId, symbol: Vec<u8>, name: Vec<u8>) -> Result<T::DomainId, DispatchError> {
+		with_transaction_result(|| Self::insert_domain(owner, symbol, name))
+	}
+
+	/// Writes as it goes, so must run inside a transaction or where a failure aborts everything
+	fn insert_domain(owner: T::AccountId, symbol: Vec<u8>, name: Vec<u8>) -> Result<T::DomainId, DispatchError> {
+		ensure!(symbol.len() > 2, Error::<T>::InvalidSymbol);
+		ensure!(name.len() > 3, Error::<T>::InvalidName);
+
+		let domain = Domain {
+			symbol,
+			name,
+			next_token_id: Zero::zero(),
+			owner: owner.clone(),
+			uri_template: None,
+			destroying: false,
+		};
+
+		let next = Self::get_next_domain_id()?;
+		Domains::<T>::insert(next, domain);
+		Self::update_deposit(next, None)?;
+		Self::deposit_event(RawEvent::DomainCreated(owner, next));
+		Ok(next)
+	}
+
+	fn do_create_token(domain_id: T::DomainId, creator: T::AccountId, total_supply: T::Balance, base_uri: Vec<u8>, royalty: Permill, max_supply: Option<T::Balance>) -> Result<T::TokenId, DispatchError> {
+		with_transaction_result(|| Self::insert_token(domain_id, creator, total_supply, base_uri, royalty, max_supply))
+	}
+
+	/// Writes as it goes, so must run inside a transaction or where a failure aborts everything
+	fn insert_token(domain_id: T::DomainId, creator: T::AccountId, total_supply: T::Balance, base_uri: Vec<u8>, royalty: Permill, max_supply: Option<T::Balance>) -> Result<T::TokenId, DispatchError> {
+		ensure!(total_supply > Zero::zero(), Error::<T>::InvalidTotalSupply);
+		ensure!(max_supply.is_none_or(|max_supply| total_supply <= max_supply), Error::<T>::InvalidMaxSupply);
+		ensure!(base_uri.len() > 3, Error::<T>::InvalidBaseUri);
+		ensure!(Domains::<T>::contains_key(domain_id), Error::<T>::InvalidDomain);
+
+		let token_id = Domains::<T>::try_mutate(domain_id, |domain| -> Result<T::TokenId, DispatchError> {
+			let next_token_id = domain.next_token_id.checked_add(&One::one()).ok_or(Error::<T>::TokenIdOverflow)?;
+			domain.next_token_id = next_token_id;
+			Ok(next_token_id)
+		})?;
+		let token = Token {
+			base_uri,
+			uri: None,
+			total_supply,
+			burned: Zero::zero(),
+			max_supply,
+			creator: creator.clone(),
+			royalty,
+			transfer_policy: TransferPolicy::Transferable,
+		};
+		Tokens::<T>::insert(domain_id, token_id, token);
+		Self::update_deposit(domain_id, Some(token_id))?;
+		Self::mint(creator.clone(), domain_id, token_id, total_supply)?;
+		Self::deposit_event(RawEvent::TokenCreated(creator, domain_id, token_id, total_supply));
+		Ok(token_id)
+	}
+
 	fn mint(to: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> Result<T::Balance, DispatchError> {
 		ensure!(Domains::<T>::contains_key(domain_id), Error::<T>::InvalidDomain);
-		Balances::<T>::try_mutate(to, (domain_id, token_id), |balance| {			
+		let new_balance = Balances::<T>::try_mutate(&to, (domain_id, token_id), |balance| -> Result<T::Balance, DispatchError> {
 			let new_balance = balance.checked_add(&quantity).ok_or(Error::<T>::BalanceOverflow)?;
 			*balance = new_balance; 
 			Ok(new_balance)
-		})	
+		})?;
+		if !new_balance.is_zero() {
+			Self::index_holding(&to, domain_id, token_id);
+		}
+		Ok(new_balance)
+	}
+
+	fn do_burn(from: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> DispatchResult {
+		Self::burn(from.clone(), domain_id, token_id, quantity)?;
+		Tokens::<T>::mutate(domain_id, token_id, |token| {
+			token.total_supply = token.total_supply.saturating_sub(quantity);
+			token.burned = token.burned.saturating_add(quantity);
+		});
+		Self::deposit_event(RawEvent::TokensBurnt(from, domain_id, token_id, quantity));
+		Ok(())
 	}
 
 	fn burn(from: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> Result<T::Balance, DispatchError> {
 		ensure!(Domains::<T>::contains_key(domain_id), Error::<T>::InvalidDomain);
-		Balances::<T>::try_mutate(from, (domain_id, token_id), |balance| {
+		let locked = Locked::<T>::get(&from, (domain_id, token_id));
+		let new_balance = Balances::<T>::try_mutate(&from, (domain_id, token_id), |balance| -> Result<T::Balance, DispatchError> {
 			let new_balance = balance.checked_sub(&quantity).ok_or(Error::<T>::BalanceOverflow)?;
+			ensure!(new_balance >= locked, Error::<T>::TokensLocked);
 			*balance = new_balance; 
 			Ok(new_balance)
-		})	
+		})?;
+		if new_balance.is_zero() {
+			Self::unindex_holding(&from, domain_id, token_id);
+		}
+		Ok(new_balance)
+	}
+
+	pub fn domain_info(domain_id: T::DomainId) -> Option<Domain<T::TokenId, T::AccountId>> {
+		if Domains::<T>::contains_key(domain_id) {
+			Some(Domains::<T>::get(domain_id))
+		} else {
+			None
+		}
+	}
+
+	pub fn token_info(domain_id: T::DomainId, token_id: T::TokenId) -> Option<Token<T::AccountId, T::Balance>> {
+		if Tokens::<T>::contains_key(domain_id, token_id) {
+			Some(Tokens::<T>::get(domain_id, token_id))
+		} else {
+			None
+		}
+	}
+
+	pub fn balance_of(owner: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) -> T::Balance {
+		Balances::<T>::get(owner, (domain_id, token_id))
+	}
+
+	/// Balances for each (owner, domain, token) in the order asked for
+	pub fn balance_of_batch(queries: Vec<(T::AccountId, T::DomainId, T::TokenId)>) -> Vec<T::Balance> {
+		queries.into_iter()
+			.map(|(owner, domain_id, token_id)| Self::balance_of(&owner, domain_id, token_id))
+			.collect()
+	}
+
+	/// Tokens in circulation, burned ones excluded
+	pub fn total_supply(domain_id: T::DomainId, token_id: T::TokenId) -> T::Balance {
+		Tokens::<T>::get(domain_id, token_id).total_supply
+	}
+
+	/// The part of an account's balance of a token that isn't locked in a listing.
+	pub fn reducible_balance(who: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) -> T::Balance {
+		Balances::<T>::get(who, (domain_id, token_id)).saturating_sub(Locked::<T>::get(who, (domain_id, token_id)))
+	}
+
+	fn do_transfer(from: T::AccountId, to: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> DispatchResult {
+		let operator = from.clone();
+		Self::do_transfer_as(&operator, from, to, domain_id, token_id, quantity)
+	}
+
+	/// Moves `from`'s tokens on behalf of `operator`, who the transfer policy is checked against
+	fn do_transfer_as(operator: &T::AccountId, from: T::AccountId, to: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> DispatchResult {
+		ensure!(Domains::<T>::contains_key(domain_id), Error::<T>::InvalidDomain);
+		ensure!(Self::reducible_balance(&from, domain_id, token_id) >= quantity, Error::<T>::InsufficientBalance);
+		Self::ensure_transferable(operator, &from, domain_id, token_id)?;
+		if from == to {
+			return Ok(());
+		}
+		Self::burn(from, domain_id, token_id, quantity)?;
+		Self::mint(to, domain_id, token_id, quantity)?;
+		Ok(())
+	}
+
+	/// Approves or revokes `operator` for each token, shared by the call and the `Domain` trait
+	pub(crate) fn do_set_approval_for_tokens(who: T::AccountId, operator: T::AccountId, domain_id: T::DomainId, token_ids: Vec<T::TokenId>, approved: bool) -> DispatchResult {
+		ensure!(who != operator, Error::<T>::InvalidOperator);
+		ensure!(!token_ids.is_empty(), Error::<T>::EmptyBatch);
+		Self::ensure_live_domain(domain_id)?;
+		ensure!(token_ids.iter().all(|token_id| Tokens::<T>::contains_key(domain_id, token_id)), Error::<T>::InvalidToken);
+		for token_id in token_ids.iter() {
+			if approved {
+				Approvals::<T>::insert((domain_id, *token_id), (who.clone(), operator.clone()), true);
+			} else {
+				Approvals::<T>::remove((domain_id, *token_id), (who.clone(), operator.clone()));
+			}
+		}
+		if approved {
+			Self::deposit_event(RawEvent::TokensApproved(who, operator, domain_id, token_ids));
+		} else {
+			Self::deposit_event(RawEvent::TokenApprovalsRevoked(who, operator, domain_id, token_ids));
+		}
+		Ok(())
+	}
+
+	/// All or nothing, a failing transfer undoes the ones before it
+	fn do_transfer_batch(from: &T::AccountId, transfers: &[BatchTransfer<T>]) -> DispatchResult {
+		with_transaction_result(|| {
+			for (domain_id, token_id, to, quantity) in transfers.iter() {
+				ensure!(*quantity > Zero::zero(), Error::<T>::InvalidQuantityToTransfer);
+				Self::do_transfer(from.clone(), to.clone(), *domain_id, *token_id, *quantity)?;
+			}
+			Ok(())
+		})
+	}
+
+	/// Whether `operator` may move `from`'s tokens, `operator` is `from` unless acting for them
+	pub(crate) fn ensure_transferable(operator: &T::AccountId, from: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) -> DispatchResult {
+		ensure!(!Frozen::<T>::get((domain_id, token_id), from), Error::<T>::AccountFrozen);
+		match Tokens::<T>::get(domain_id, token_id).transfer_policy {
+			TransferPolicy::Transferable => Ok(()),
+			TransferPolicy::Soulbound => Err(Error::<T>::TokenNotTransferable.into()),
+			TransferPolicy::DomainOwnerOnly => {
+				ensure!(Domains::<T>::get(domain_id).owner == *operator, Error::<T>::TransferRestricted);
+				Ok(())
+			},
+		}
+	}
+
+	/// The domain owner holds every role and admins hold every role apart from granting admin.
+	pub fn has_role(domain_id: T::DomainId, who: &T::AccountId, role: Role) -> bool {
+		Domains::<T>::get(domain_id).owner == *who
+			|| Roles::<T>::get(domain_id, (who.clone(), Role::Admin))
+			|| Roles::<T>::get(domain_id, (who.clone(), role))
+	}
+
+	fn ensure_role(domain_id: T::DomainId, who: &T::AccountId, role: Role) -> DispatchResult {
+		Self::ensure_live_domain(domain_id)?;
+		ensure!(Self::has_role(domain_id, who, role), Error::<T>::MissingRole);
+		Ok(())
+	}
+
+	/// Nothing new can be attached to a domain once its teardown has started
+	fn ensure_live_domain(domain_id: T::DomainId) -> DispatchResult {
+		ensure!(Domains::<T>::contains_key(domain_id), Error::<T>::InvalidDomain);
+		ensure!(!Domains::<T>::get(domain_id).destroying, Error::<T>::DomainDestroying);
+		Ok(())
+	}
+
+	fn ensure_can_manage_role(domain_id: T::DomainId, who: &T::AccountId, role: Role) -> DispatchResult {
+		ensure!(Domains::<T>::contains_key(domain_id), Error::<T>::InvalidDomain);
+		if role == Role::Admin {
+			ensure!(Domains::<T>::get(domain_id).owner == *who, Error::<T>::NotDomainOwner);
+		} else {
+			ensure!(Self::has_role(domain_id, who, Role::Admin), Error::<T>::MissingRole);
+		}
+		Ok(())
+	}
+
+	pub fn is_approved(owner: &T::AccountId, operator: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) -> bool {
+		owner == operator
+			|| Operators::<T>::get(domain_id, (owner.clone(), operator.clone()))
+			|| Approvals::<T>::get((domain_id, token_id), (owner.clone(), operator.clone()))
+	}
+
+	/// The amount `spender` may still move out of `owner`'s balance, zero once the allowance has expired.
+	pub fn allowance(owner: &T::AccountId, spender: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) -> T::Balance {
+		Self::current_allowance(owner, spender, domain_id, token_id)
+			.ok()
+			.flatten()
+			.map(|allowance| allowance.amount)
+			.unwrap_or_else(Zero::zero)
+	}
+
+	fn current_allowance(owner: &T::AccountId, spender: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) -> Result<Option<AllowanceOf<T>>, DispatchError> {
+		match Allowances::<T>::get((domain_id, token_id), (owner.clone(), spender.clone())) {
+			Some(allowance) => {
+				if let Some(expires_at) = allowance.expires_at {
+					ensure!(expires_at > frame_system::Module::<T>::block_number(), Error::<T>::AllowanceExpired);
+				}
+				Ok(Some(allowance))
+			},
+			None => Ok(None),
+		}
+	}
+
+	fn set_allowance(owner: &T::AccountId, spender: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, allowance: AllowanceOf<T>) {
+		let amount = allowance.amount;
+		let expires_at = allowance.expires_at;
+		if amount.is_zero() {
+			Allowances::<T>::remove((domain_id, token_id), (owner.clone(), spender.clone()));
+		} else {
+			Allowances::<T>::insert((domain_id, token_id), (owner.clone(), spender.clone()), allowance);
+		}
+		Self::deposit_event(RawEvent::AllowanceSet(owner.clone(), spender.clone(), domain_id, token_id, amount, expires_at));
+	}
+
+	fn spend_allowance(owner: &T::AccountId, spender: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> DispatchResult {
+		let mut allowance = Self::current_allowance(owner, spender, domain_id, token_id)?.ok_or(Error::<T>::NotApproved)?;
+		allowance.amount = allowance.amount.checked_sub(&quantity).ok_or(Error::<T>::InsufficientAllowance)?;
+		Self::set_allowance(owner, spender, domain_id, token_id, allowance);
+		Ok(())
 	}
 
 	fn get_next_domain_id() -> Result<T::DomainId, DispatchError> {
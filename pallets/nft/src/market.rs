@@ -1,24 +1,311 @@
 use frame_support::{
     dispatch::{result::Result, DispatchError, DispatchResult},
-    traits::Get,
+    ensure,
+    traits::{BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency},
+    IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use sp_runtime::{
+    traits::{CheckedMul, Saturating, Zero},
+    SaturatedConversion,
 };
-
 use sp_std::vec::Vec;
 
-pub trait Market {
+use crate::{with_transaction_result, AuctionId, BalanceOf, Bid, BidId, BidOf, Bids, DutchAuction, DutchAuctionOf, DutchAuctions, Error, Listing, Listings, Locked, Module, NextAuctionId, NextBidId, RawEvent, Tokens, Trait};
+
+pub trait Market<AccountId> {
     type DomainId;
     type ItemId;
     type Balance;
+    type Price;
+
+    /// Put an item on the market to sell.  The offer is the price required per item.  Amount is the amount
+    /// we want to offer to sell, which is locked until it is sold or the offer is cancelled.
+    fn offer_on_item(seller: &AccountId, domain_id: &Self::DomainId, item_id: &Self::ItemId, amount: Self::Balance, offer: Self::Price) -> DispatchResult;
+    /// Take an item off the market, unlocking whatever hasn't been sold
+    fn cancel_offer_on_item(seller: &AccountId, domain_id: &Self::DomainId, item_id: &Self::ItemId) -> DispatchResult;
+    /// Buy part or all of a seller's offer, paying the offer price for each item
+    fn buy_item(buyer: &AccountId, domain_id: &Self::DomainId, item_id: &Self::ItemId, seller: &AccountId, amount: Self::Balance) -> DispatchResult;
+}
+
+pub trait BidBook<AccountId> {
+    type DomainId;
+    type ItemId;
+    type Balance;
+    type Price;
+    type BidId;
     type Bid;
 
-    /// Put an item on the market to sell.  The offer is the minimum amount required per item.  Amount is the amount
-    /// we want to offer to sell.
-    fn offer_on_item(domain_id: &Self::DomainId, item_id: &Self::ItemId, amount: u64, offer: &Self::Balance);
     /// Offer on an item in the market to buy.  If we offer the buy now price or more then this is transacted immediately else it
-    /// goes on the book to be approved by seller
-    fn bid_on_item(domain_id: &Self::DomainId, item_id: &Self::ItemId, amount: u64, offer: &Self::Balance);
+    /// goes on the book to be approved by seller, with the offer reserved from the bidder until then
+    fn bid_on_item(bidder: &AccountId, domain_id: &Self::DomainId, item_id: &Self::ItemId, seller: &AccountId, amount: Self::Balance, offer: Self::Price) -> Result<Option<Self::BidId>, DispatchError>;
     /// A list of the bids on an item
-    fn list_bids_for_item(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> Vec<Self::Bid>;
+    fn list_bids_for_item(domain_id: &Self::DomainId, item_id: &Self::ItemId) -> Vec<(Self::BidId, Self::Bid)>;
     /// Accept or reject an item that has a bid
-    fn accept_bid_for_item(domain_id: &Self::DomainId, item_id: &Self::ItemId, bid: &Self::Bid);
-}
\ No newline at end of file
+    fn accept_bid_for_item(seller: &AccountId, domain_id: &Self::DomainId, item_id: &Self::ItemId, bid_id: &Self::BidId, accept: bool) -> DispatchResult;
+}
+
+impl<T: Trait> Market<T::AccountId> for Module<T> {
+    type DomainId = T::DomainId;
+    type ItemId = T::TokenId;
+    type Balance = T::Balance;
+    type Price = BalanceOf<T>;
+
+    fn offer_on_item(seller: &T::AccountId, domain_id: &T::DomainId, item_id: &T::TokenId, amount: T::Balance, offer: BalanceOf<T>) -> DispatchResult {
+        Self::do_list(seller.clone(), *domain_id, *item_id, amount, offer)
+    }
+
+    fn cancel_offer_on_item(seller: &T::AccountId, domain_id: &T::DomainId, item_id: &T::TokenId) -> DispatchResult {
+        Self::do_cancel_listing(seller.clone(), *domain_id, *item_id)
+    }
+
+    fn buy_item(buyer: &T::AccountId, domain_id: &T::DomainId, item_id: &T::TokenId, seller: &T::AccountId, amount: T::Balance) -> DispatchResult {
+        with_transaction_result(|| Self::do_buy(buyer.clone(), *domain_id, *item_id, seller.clone(), amount))
+    }
+}
+
+impl<T: Trait> BidBook<T::AccountId> for Module<T> {
+    type DomainId = T::DomainId;
+    type ItemId = T::TokenId;
+    type Balance = T::Balance;
+    type Price = BalanceOf<T>;
+    type BidId = BidId;
+    type Bid = BidOf<T>;
+
+    fn bid_on_item(bidder: &T::AccountId, domain_id: &T::DomainId, item_id: &T::TokenId, seller: &T::AccountId, amount: T::Balance, offer: BalanceOf<T>) -> Result<Option<BidId>, DispatchError> {
+        with_transaction_result(|| Self::do_place_bid(bidder.clone(), *domain_id, *item_id, seller.clone(), amount, offer))
+    }
+
+    fn list_bids_for_item(domain_id: &T::DomainId, item_id: &T::TokenId) -> Vec<(BidId, Self::Bid)> {
+        Self::bids_for_token(*domain_id, *item_id)
+    }
+
+    fn accept_bid_for_item(seller: &T::AccountId, domain_id: &T::DomainId, item_id: &T::TokenId, bid_id: &BidId, accept: bool) -> DispatchResult {
+        with_transaction_result(|| Self::do_settle_bid(seller.clone(), *domain_id, *item_id, *bid_id, accept))
+    }
+}
+
+impl<T: Trait> Module<T> {
+    pub(crate) fn do_list(seller: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance, price: BalanceOf<T>) -> DispatchResult {
+        ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToList);
+        ensure!(price > Zero::zero(), Error::<T>::InvalidPrice);
+        Self::price_for(quantity, price)?;
+        Self::ensure_transferable(&seller, &seller, domain_id, token_id)?;
+
+        // Relisting replaces the previous listing, so only lock the difference
+        let listed = Listings::<T>::get((domain_id, token_id), &seller).map(|listing| listing.quantity).unwrap_or_else(Zero::zero);
+        if quantity > listed {
+            Self::lock(&seller, domain_id, token_id, quantity - listed)?;
+        } else {
+            Self::unlock(&seller, domain_id, token_id, listed - quantity);
+        }
+        Listings::<T>::insert((domain_id, token_id), &seller, Listing { quantity, price });
+        Self::deposit_event(RawEvent::TokensListed(seller, domain_id, token_id, quantity, price));
+        Ok(())
+    }
+
+    pub(crate) fn do_cancel_listing(seller: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId) -> DispatchResult {
+        let listing = Listings::<T>::take((domain_id, token_id), &seller).ok_or(Error::<T>::ListingNotFound)?;
+        Self::unlock(&seller, domain_id, token_id, listing.quantity);
+        Self::deposit_event(RawEvent::ListingCancelled(seller, domain_id, token_id));
+        Ok(())
+    }
+
+    /// Must be called from a transactional context, payment is taken before the tokens move.
+    pub(crate) fn do_buy(buyer: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, seller: T::AccountId, quantity: T::Balance) -> DispatchResult {
+        ensure!(buyer != seller, Error::<T>::CannotBuyOwnListing);
+        ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToTransfer);
+        let listing = Listings::<T>::get((domain_id, token_id), &seller).ok_or(Error::<T>::ListingNotFound)?;
+        ensure!(quantity <= listing.quantity, Error::<T>::InsufficientListingQuantity);
+
+        let total = Self::price_for(quantity, listing.price)?;
+        Self::pay_for_sale(&buyer, &seller, domain_id, token_id, total, false)?;
+        Self::fill_listing(&seller, &buyer, domain_id, token_id, quantity)?;
+        Self::deposit_event(RawEvent::TokensSold(seller, buyer, domain_id, token_id, quantity, total));
+        Ok(())
+    }
+
+    /// Returns the id of the bid if it went on the book, or `None` if it was filled straight away.
+    /// Must be called from a transactional context.
+    pub(crate) fn do_place_bid(bidder: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, seller: T::AccountId, quantity: T::Balance, price: BalanceOf<T>) -> Result<Option<BidId>, DispatchError> {
+        ensure!(bidder != seller, Error::<T>::CannotBuyOwnListing);
+        ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToTransfer);
+        ensure!(price > Zero::zero(), Error::<T>::InvalidPrice);
+        let listing = Listings::<T>::get((domain_id, token_id), &seller).ok_or(Error::<T>::ListingNotFound)?;
+        ensure!(quantity <= listing.quantity, Error::<T>::InsufficientListingQuantity);
+
+        // Offering the listing price or more buys straight away at the listing price
+        if price >= listing.price {
+            Self::do_buy(bidder, domain_id, token_id, seller, quantity)?;
+            return Ok(None);
+        }
+
+        let total = Self::price_for(quantity, price)?;
+        let bid_id = NextBidId::try_mutate(|next_id| -> Result<BidId, DispatchError> {
+            let current_id = *next_id;
+            *next_id = next_id.checked_add(1).ok_or(Error::<T>::BidIdOverflow)?;
+            Ok(current_id)
+        })?;
+        T::Currency::reserve(&bidder, total)?;
+        Bids::<T>::insert((domain_id, token_id), bid_id, Bid { bidder: bidder.clone(), seller: seller.clone(), quantity, price });
+        Self::deposit_event(RawEvent::BidPlaced(bid_id, bidder, seller, domain_id, token_id, quantity, price));
+        Ok(Some(bid_id))
+    }
+
+    /// Must be called from a transactional context when accepting.
+    pub(crate) fn do_settle_bid(seller: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, bid_id: BidId, accept: bool) -> DispatchResult {
+        let bid = Bids::<T>::get((domain_id, token_id), bid_id).ok_or(Error::<T>::BidNotFound)?;
+        ensure!(bid.seller == seller, Error::<T>::NotBidSeller);
+        let total = Self::price_for(bid.quantity, bid.price)?;
+        Bids::<T>::remove((domain_id, token_id), bid_id);
+
+        if accept {
+            Self::pay_for_sale(&bid.bidder, &seller, domain_id, token_id, total, true)?;
+            Self::fill_listing(&seller, &bid.bidder, domain_id, token_id, bid.quantity)?;
+            Self::deposit_event(RawEvent::BidAccepted(bid_id, seller, bid.bidder, domain_id, token_id, bid.quantity, total));
+        } else {
+            T::Currency::unreserve(&bid.bidder, total);
+            Self::deposit_event(RawEvent::BidRejected(bid_id, domain_id, token_id));
+        }
+        Ok(())
+    }
+
+    /// All the bids waiting on the book for a token.
+    pub fn bids_for_token(domain_id: T::DomainId, token_id: T::TokenId) -> Vec<(BidId, BidOf<T>)> {
+        Bids::<T>::iter_prefix((domain_id, token_id)).collect()
+    }
+
+    /// Moves `quantity` listed tokens from the seller to the buyer once they have been paid for.
+    fn fill_listing(seller: &T::AccountId, buyer: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> DispatchResult {
+        let mut listing = Listings::<T>::get((domain_id, token_id), seller).ok_or(Error::<T>::ListingNotFound)?;
+        ensure!(quantity <= listing.quantity, Error::<T>::InsufficientListingQuantity);
+        Self::unlock(seller, domain_id, token_id, quantity);
+        Self::do_transfer(seller.clone(), buyer.clone(), domain_id, token_id, quantity)?;
+
+        listing.quantity -= quantity;
+        if listing.quantity.is_zero() {
+            Listings::<T>::remove((domain_id, token_id), seller);
+        } else {
+            Listings::<T>::insert((domain_id, token_id), seller, listing);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn do_create_dutch_auction(seller: T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance, start_price: BalanceOf<T>, floor_price: BalanceOf<T>, decay_per_block: BalanceOf<T>) -> Result<AuctionId, DispatchError> {
+        ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToList);
+        ensure!(floor_price > Zero::zero() && start_price >= floor_price, Error::<T>::InvalidPrice);
+        Self::price_for(quantity, start_price)?;
+        Self::ensure_transferable(&seller, &seller, domain_id, token_id)?;
+
+        let auction_id = NextAuctionId::try_mutate(|next_id| -> Result<AuctionId, DispatchError> {
+            let current_id = *next_id;
+            *next_id = next_id.checked_add(1).ok_or(Error::<T>::AuctionIdOverflow)?;
+            Ok(current_id)
+        })?;
+        Self::lock(&seller, domain_id, token_id, quantity)?;
+        DutchAuctions::<T>::insert(auction_id, DutchAuction {
+            seller: seller.clone(),
+            domain_id,
+            token_id,
+            quantity,
+            start_price,
+            floor_price,
+            decay_per_block,
+            start: frame_system::Module::<T>::block_number(),
+        });
+        Self::deposit_event(RawEvent::DutchAuctionCreated(auction_id, seller, domain_id, token_id, quantity, start_price, floor_price, decay_per_block));
+        Ok(auction_id)
+    }
+
+    /// Must be called from a transactional context, payment is taken before the tokens move.
+    pub(crate) fn do_buy_from_dutch_auction(buyer: T::AccountId, auction_id: AuctionId, quantity: T::Balance, max_price: BalanceOf<T>) -> DispatchResult {
+        let mut auction = DutchAuctions::<T>::get(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+        ensure!(buyer != auction.seller, Error::<T>::CannotBuyOwnListing);
+        ensure!(quantity > Zero::zero(), Error::<T>::InvalidQuantityToTransfer);
+        ensure!(quantity <= auction.quantity, Error::<T>::InsufficientListingQuantity);
+
+        let price = Self::dutch_auction_price(&auction);
+        ensure!(price <= max_price, Error::<T>::PriceAboveLimit);
+        let total = Self::price_for(quantity, price)?;
+        Self::pay_for_sale(&buyer, &auction.seller, auction.domain_id, auction.token_id, total, false)?;
+        Self::unlock(&auction.seller, auction.domain_id, auction.token_id, quantity);
+        Self::do_transfer(auction.seller.clone(), buyer.clone(), auction.domain_id, auction.token_id, quantity)?;
+
+        auction.quantity -= quantity;
+        if auction.quantity.is_zero() {
+            DutchAuctions::<T>::remove(auction_id);
+        } else {
+            DutchAuctions::<T>::insert(auction_id, auction);
+        }
+        Self::deposit_event(RawEvent::DutchAuctionPurchase(auction_id, buyer, quantity, price));
+        Ok(())
+    }
+
+    pub(crate) fn do_close_dutch_auction(seller: T::AccountId, auction_id: AuctionId) -> DispatchResult {
+        let auction = DutchAuctions::<T>::get(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+        ensure!(auction.seller == seller, Error::<T>::NotAuctionSeller);
+        Self::unlock(&seller, auction.domain_id, auction.token_id, auction.quantity);
+        DutchAuctions::<T>::remove(auction_id);
+        Self::deposit_event(RawEvent::DutchAuctionClosed(auction_id));
+        Ok(())
+    }
+
+    /// The price per token of a dutch auction at the current block, decaying from the start price
+    /// down to the floor.
+    pub fn dutch_auction_price(auction: &DutchAuctionOf<T>) -> BalanceOf<T> {
+        let elapsed = frame_system::Module::<T>::block_number().saturating_sub(auction.start);
+        let elapsed: BalanceOf<T> = elapsed.saturated_into::<u128>().saturated_into();
+        auction.start_price
+            .saturating_sub(auction.decay_per_block.saturating_mul(elapsed))
+            .max(auction.floor_price)
+    }
+
+    /// Pays the seller for a sale, less the royalty owed to the token's creator. Payment comes out of
+    /// the buyer's reserved balance when `reserved` is set.
+    pub(crate) fn pay_for_sale(buyer: &T::AccountId, seller: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, total: BalanceOf<T>, reserved: bool) -> DispatchResult {
+        let (creator, royalty) = Self::royalty_info(domain_id, token_id, total).ok_or(Error::<T>::InvalidToken)?;
+        let royalty = if creator == *seller { Zero::zero() } else { royalty };
+        let proceeds = total.saturating_sub(royalty);
+
+        if reserved {
+            T::Currency::repatriate_reserved(buyer, seller, proceeds, BalanceStatus::Free)?;
+            if !royalty.is_zero() {
+                T::Currency::repatriate_reserved(buyer, &creator, royalty, BalanceStatus::Free)?;
+            }
+        } else {
+            T::Currency::transfer(buyer, seller, proceeds, ExistenceRequirement::KeepAlive)?;
+            if !royalty.is_zero() {
+                T::Currency::transfer(buyer, &creator, royalty, ExistenceRequirement::KeepAlive)?;
+            }
+        }
+
+        if !royalty.is_zero() {
+            Self::deposit_event(RawEvent::RoyaltyPaid(creator, domain_id, token_id, royalty));
+        }
+        Ok(())
+    }
+
+    /// The creator of a token and the royalty they are owed on a sale at `sale_price`.
+    pub fn royalty_info(domain_id: T::DomainId, token_id: T::TokenId, sale_price: BalanceOf<T>) -> Option<(T::AccountId, BalanceOf<T>)> {
+        if !Tokens::<T>::contains_key(domain_id, token_id) {
+            return None;
+        }
+        let token = Tokens::<T>::get(domain_id, token_id);
+        Some((token.creator, token.royalty * sale_price))
+    }
+
+    /// The cost of `quantity` tokens at `price` each.
+    pub fn price_for(quantity: T::Balance, price: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let quantity: BalanceOf<T> = quantity.saturated_into::<u128>().saturated_into();
+        price.checked_mul(&quantity).ok_or_else(|| Error::<T>::PriceOverflow.into())
+    }
+
+    pub(crate) fn lock(who: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) -> DispatchResult {
+        ensure!(Self::reducible_balance(who, domain_id, token_id) >= quantity, Error::<T>::InsufficientBalance);
+        Locked::<T>::mutate(who, (domain_id, token_id), |locked| *locked = locked.saturating_add(quantity));
+        Ok(())
+    }
+
+    pub(crate) fn unlock(who: &T::AccountId, domain_id: T::DomainId, token_id: T::TokenId, quantity: T::Balance) {
+        Locked::<T>::mutate(who, (domain_id, token_id), |locked| *locked = locked.saturating_sub(quantity));
+    }
+}
@@ -0,0 +1,81 @@
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::Get,
+    StorageDoubleMap, StorageMap,
+};
+use sp_runtime::SaturatedConversion;
+use sp_std::vec::Vec;
+
+use crate::{with_transaction_result, Domains, Error, Module, RawEvent, Tokens, Trait};
+
+/// Used when a domain has no template of its own
+const DEFAULT_URI_TEMPLATE: &[u8] = b"{base}/{id}";
+
+impl<T: Trait> Module<T> {
+    pub(crate) fn do_set_token_uri(domain_id: T::DomainId, token_id: T::TokenId, uri: Option<Vec<u8>>) -> DispatchResult {
+        ensure!(Tokens::<T>::contains_key(domain_id, token_id), Error::<T>::InvalidToken);
+        ensure!(uri.as_ref().is_none_or(|uri| uri.len() as u32 <= T::ValueLimit::get()), Error::<T>::ValueTooLong);
+        with_transaction_result(|| {
+            Tokens::<T>::mutate(domain_id, token_id, |token| token.uri = uri.clone());
+            Self::update_deposit(domain_id, Some(token_id))
+        })?;
+        Self::deposit_event(RawEvent::TokenUriSet(domain_id, token_id, uri));
+        Ok(())
+    }
+
+    pub(crate) fn do_set_uri_template(domain_id: T::DomainId, uri_template: Option<Vec<u8>>) -> DispatchResult {
+        ensure!(uri_template.as_ref().is_none_or(|template| template.len() as u32 <= T::ValueLimit::get()), Error::<T>::ValueTooLong);
+        with_transaction_result(|| {
+            Domains::<T>::mutate(domain_id, |domain| domain.uri_template = uri_template.clone());
+            Self::update_deposit(domain_id, None)
+        })?;
+        Self::deposit_event(RawEvent::UriTemplateSet(domain_id, uri_template));
+        Ok(())
+    }
+
+    /// The token's own URI if it has one, otherwise the domain template filled in with
+    /// `{base}` as the token's base uri and `{id}` as the token id
+    pub fn token_uri(domain_id: T::DomainId, token_id: T::TokenId) -> Option<Vec<u8>> {
+        if !Tokens::<T>::contains_key(domain_id, token_id) {
+            return None;
+        }
+        let token = Tokens::<T>::get(domain_id, token_id);
+        if let Some(uri) = token.uri {
+            return Some(uri);
+        }
+        let template = Domains::<T>::get(domain_id).uri_template.unwrap_or_else(|| DEFAULT_URI_TEMPLATE.to_vec());
+        Some(Self::fill_uri_template(&template, &token.base_uri, &Self::encode_decimal(token_id.saturated_into())))
+    }
+
+    fn fill_uri_template(template: &[u8], base: &[u8], id: &[u8]) -> Vec<u8> {
+        let mut uri = Vec::with_capacity(template.len() + base.len() + id.len());
+        let mut rest = template;
+        while !rest.is_empty() {
+            if rest.starts_with(b"{base}") {
+                uri.extend_from_slice(base);
+                rest = &rest[6..];
+            } else if rest.starts_with(b"{id}") {
+                uri.extend_from_slice(id);
+                rest = &rest[4..];
+            } else {
+                uri.push(rest[0]);
+                rest = &rest[1..];
+            }
+        }
+        uri
+    }
+
+    fn encode_decimal(mut n: u128) -> Vec<u8> {
+        let mut digits = Vec::new();
+        loop {
+            digits.push(b'0' + (n % 10) as u8);
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        digits.reverse();
+        digits
+    }
+}
@@ -0,0 +1,207 @@
+//! Storage migrations for the nft pallet, run from `on_runtime_upgrade` in version order. The
+//! V3 index build is spread over the blocks that follow, see [`v3`].
+
+use frame_support::{
+    debug,
+    storage::migration::StorageIterator,
+    traits::Get,
+    weights::Weight,
+    IterableStorageDoubleMap, IterableStorageMap, StorageValue,
+};
+
+use crate::{Module, Releases, StorageVersion, Trait};
+
+pub fn migrate<T: Trait>() -> Weight {
+    let mut weight = T::DbWeight::get().reads(1);
+    if StorageVersion::get() == Releases::V1_0_0 {
+        weight = weight.saturating_add(match Module::<T>::migrate_to_v2_checked() {
+            Ok(weight) => weight,
+            Err(err) => {
+                // The translation may have run before the check failed, so assume the worst
+                debug::error!("nft v2 migration failed: {}", err);
+                T::MaximumBlockWeight::get()
+            }
+        });
+    }
+    if StorageVersion::get() == Releases::V2_0_0 {
+        weight = weight.saturating_add(v3::start::<T>());
+    }
+    weight
+}
+
+/// Counts entries under a storage item of the `NFT` prefix that decode as `V`
+fn count_entries<V: codec::Decode>(item: &[u8]) -> u32 {
+    StorageIterator::<V>::new(b"NFT", item).count() as u32
+}
+
+/// Fills in the fields added to `Domain` and `Token` since the first release.
+pub mod v2 {
+    use codec::{Decode, Encode};
+    use sp_runtime::{traits::Zero, Permill};
+    use sp_std::{cell::Cell, vec::Vec};
+
+    use super::*;
+    use crate::{Domain, Domains, Token, Tokens, TransferPolicy};
+
+    #[derive(Encode, Decode)]
+    pub struct OldDomain<TokenId, AccountId> {
+        pub symbol: Vec<u8>,
+        pub name: Vec<u8>,
+        pub next_token_id: TokenId,
+        pub owner: AccountId,
+    }
+
+    #[derive(Encode, Decode)]
+    pub struct OldToken<AccountId, Balance> {
+        pub base_uri: Vec<u8>,
+        pub total_supply: Balance,
+        pub creator: AccountId,
+    }
+
+    /// Returns how many domains and tokens are waiting to be migrated
+    pub fn pre_migrate<T: Trait>() -> Result<(u32, u32), &'static str> {
+        if StorageVersion::get() != Releases::V1_0_0 {
+            return Err("nft storage is not at V1_0_0");
+        }
+        let domains = count_entries::<OldDomain<T::TokenId, T::AccountId>>(b"Domains");
+        let tokens = count_entries::<OldToken<T::AccountId, T::Balance>>(b"Tokens");
+        Ok((domains, tokens))
+    }
+
+    pub fn migrate<T: Trait>() -> Weight {
+        // `translate` takes an `Fn`, so the count can't be a plain captured counter
+        let translated = Cell::new(0 as Weight);
+        Domains::<T>::translate::<OldDomain<T::TokenId, T::AccountId>, _>(|_, old| {
+            translated.set(translated.get() + 1);
+            Some(Domain {
+                symbol: old.symbol,
+                name: old.name,
+                next_token_id: old.next_token_id,
+                owner: old.owner,
+                uri_template: None,
+                destroying: false,
+            })
+        });
+        Tokens::<T>::translate::<OldToken<T::AccountId, T::Balance>, _>(|_, _, old| {
+            translated.set(translated.get() + 1);
+            Some(Token {
+                base_uri: old.base_uri,
+                uri: None,
+                total_supply: old.total_supply,
+                burned: Zero::zero(),
+                max_supply: None,
+                creator: old.creator,
+                royalty: Permill::zero(),
+                transfer_policy: TransferPolicy::Transferable,
+            })
+        });
+        StorageVersion::put(Releases::V2_0_0);
+        let translated = translated.get();
+        T::DbWeight::get().reads_writes(translated, translated.saturating_add(1))
+    }
+
+    /// Checks every entry counted by `pre_migrate` decodes in the new layout
+    pub fn post_migrate<T: Trait>(domains: u32, tokens: u32) -> Result<(), &'static str> {
+        if StorageVersion::get() != Releases::V2_0_0 {
+            return Err("nft storage was not bumped to V2_0_0");
+        }
+        if Domains::<T>::iter().count() as u32 != domains {
+            return Err("domains were lost in the migration");
+        }
+        if Tokens::<T>::iter().count() as u32 != tokens {
+            return Err("tokens were lost in the migration");
+        }
+        Ok(())
+    }
+
+    impl<T: Trait> Module<T> {
+        /// Migrates straight from V1_0_0, counting the entries before and after to check none
+        /// were lost. This is what `on_runtime_upgrade` runs.
+        pub fn migrate_to_v2_checked() -> Result<Weight, &'static str> {
+            let (domains, tokens) = pre_migrate::<T>()?;
+            let weight = migrate::<T>();
+            post_migrate::<T>(domains, tokens)?;
+            // Both checks read every domain and token once more
+            let checked = (domains as Weight).saturating_add(tokens as Weight).saturating_mul(2);
+            Ok(weight.saturating_add(T::DbWeight::get().reads(checked)))
+        }
+    }
+}
+
+/// Builds the holder and owned token indexes from the balances already in storage.
+///
+/// There's no bound on how many balances a chain holds, so the upgrade only starts the walk
+/// and `on_initialize` indexes up to `INDEX_PER_BLOCK` of them a block from then on, keeping
+/// its place in `IndexCursor`. Storage stays at V2 and `tokens_of`/`holders_of` may miss
+/// holdings until the walk is done. Transfers index as they go in the meantime, and indexing
+/// a holding twice does nothing.
+pub mod v3 {
+    use codec::Decode;
+    use frame_support::{
+        sp_io,
+        storage::{unhashed, StoragePrefixedMap},
+    };
+    use sp_runtime::traits::Zero;
+
+    use super::*;
+    use crate::{Balances, IndexCursor};
+
+    /// Most balances indexed in a block. Finding and reading each costs two reads, indexing it
+    /// three more and six writes.
+    pub const INDEX_PER_BLOCK: u32 = 256;
+
+    /// Points the cursor at the start of `Balances`
+    pub fn start<T: Trait>() -> Weight {
+        IndexCursor::put(Balances::<T>::final_prefix().to_vec());
+        T::DbWeight::get().writes(1)
+    }
+
+    /// Indexes up to `limit` more balances, bumping storage to V3 once none are left
+    pub fn step<T: Trait>(limit: u32) -> Weight {
+        let mut cursor = match IndexCursor::get() {
+            Some(cursor) => cursor,
+            None => return T::DbWeight::get().reads(1),
+        };
+        let prefix = Balances::<T>::final_prefix();
+        let mut visited: Weight = 0;
+        let mut indexed: Weight = 0;
+        let mut finished = false;
+        for _ in 0..limit {
+            let key = match sp_io::storage::next_key(&cursor).filter(|key| key.starts_with(&prefix)) {
+                Some(key) => key,
+                None => {
+                    finished = true;
+                    break;
+                }
+            };
+            visited += 1;
+            let balance = unhashed::get::<T::Balance>(&key).unwrap_or_else(Zero::zero);
+            if let Some((who, domain_id, token_id)) = decode_key::<T>(&key[prefix.len()..]) {
+                if !balance.is_zero() {
+                    indexed += 1;
+                    Module::<T>::index_holding(&who, domain_id, token_id);
+                }
+            }
+            cursor = key;
+        }
+        // A batch that ends on the last balance is only noticed as finished the block after
+        if finished {
+            IndexCursor::kill();
+            StorageVersion::put(Releases::V3_0_0);
+        } else {
+            IndexCursor::put(cursor);
+        }
+        let reads = visited.saturating_add(1).saturating_mul(2).saturating_add(indexed.saturating_mul(3));
+        T::DbWeight::get().reads_writes(reads, indexed.saturating_mul(6).saturating_add(2))
+    }
+
+    /// Splits a `Balances` key, past the prefix, into the account and token it's for. Both
+    /// halves are `blake2_128_concat`, so the raw key follows each 16 byte hash.
+    fn decode_key<T: Trait>(key: &[u8]) -> Option<(T::AccountId, T::DomainId, T::TokenId)> {
+        let mut input = key.get(16..)?;
+        let who = T::AccountId::decode(&mut input).ok()?;
+        let mut input = input.get(16..)?;
+        let (domain_id, token_id) = <(T::DomainId, T::TokenId)>::decode(&mut input).ok()?;
+        Some((who, domain_id, token_id))
+    }
+}
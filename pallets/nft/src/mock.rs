@@ -5,11 +5,12 @@ use frame_support::{
 	impl_outer_event, 
 	parameter_types, 
 	weights::Weight,
-	traits::{OnInitialize, OnFinalize},
+	traits::{Get, OnInitialize, OnFinalize},
 };
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 };
+use std::cell::RefCell;
 
 impl_outer_origin! {
 	pub enum Origin for Test where system = frame_system {}
@@ -86,11 +87,57 @@ impl pallet_timestamp::Trait for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const AuctionExtendPeriod: u64 = 5;
+	pub const MaxAuctionsEndingPerBlock: u32 = 2;
+	pub const KeyLimit: u32 = 16;
+	pub const ValueLimit: u32 = 32;
+	pub const AttributeDepositBase: u64 = 10;
+}
+
+// Deposits are off unless a test turns them on, so balances elsewhere stay simple
+thread_local! {
+	static DOMAIN_DEPOSIT_BASE: RefCell<u64> = const { RefCell::new(0) };
+	static TOKEN_DEPOSIT_BASE: RefCell<u64> = const { RefCell::new(0) };
+	static DEPOSIT_PER_BYTE: RefCell<u64> = const { RefCell::new(0) };
+}
+
+pub struct DomainDepositBase;
+impl Get<u64> for DomainDepositBase {
+	fn get() -> u64 { DOMAIN_DEPOSIT_BASE.with(|v| *v.borrow()) }
+}
+
+pub struct TokenDepositBase;
+impl Get<u64> for TokenDepositBase {
+	fn get() -> u64 { TOKEN_DEPOSIT_BASE.with(|v| *v.borrow()) }
+}
+
+pub struct DepositPerByte;
+impl Get<u64> for DepositPerByte {
+	fn get() -> u64 { DEPOSIT_PER_BYTE.with(|v| *v.borrow()) }
+}
+
+pub fn set_deposits(domain_base: u64, token_base: u64, per_byte: u64) {
+	DOMAIN_DEPOSIT_BASE.with(|v| *v.borrow_mut() = domain_base);
+	TOKEN_DEPOSIT_BASE.with(|v| *v.borrow_mut() = token_base);
+	DEPOSIT_PER_BYTE.with(|v| *v.borrow_mut() = per_byte);
+}
+
 impl Trait for Test {
 	type Event = Event;
 	type DomainId = u64;
 	type TokenId = u64;
 	type Balance = u64;
+	type Currency = Balances;
+	type AuctionExtendPeriod = AuctionExtendPeriod;
+	type MaxAuctionsEndingPerBlock = MaxAuctionsEndingPerBlock;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DomainDepositBase = DomainDepositBase;
+	type TokenDepositBase = TokenDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type WeightInfo = ();
 }
 
 pub type NftModule = Module<Test>;
@@ -100,15 +147,14 @@ pub type Timestamp = pallet_timestamp::Module<Test>;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
+	set_deposits(0, 0, 0);
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
     pallet_balances::GenesisConfig::<Test>{
-		balances: vec![(200, 500)],
+		balances: vec![(100, 10_000), (101, 10_000), (102, 10_000), (200, 500)],
     }.assimilate_storage(&mut t).unwrap();
 
-    frame_system::GenesisConfig::default().assimilate_storage::<Test>(&mut t).unwrap();
-    
-	let mut t: sp_io::TestExternalities = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	let mut t: sp_io::TestExternalities = t.into();
     t.execute_with(|| System::set_block_number(1) );
     t
 }
@@ -120,11 +166,10 @@ pub fn last_event() -> Event {
 
 pub fn run_to_block(n: u64) {
     while System::block_number() < n {
-        // TimeKeeperModule::on_finalize(System::block_number());
         System::on_finalize(System::block_number());
         System::set_block_number(System::block_number() + 1);
         System::on_initialize(System::block_number());
-        // TimeKeeperModule::on_initialize(System::block_number());
+        NftModule::on_initialize(System::block_number());
 	}
 	
 	Timestamp::set_timestamp(n);
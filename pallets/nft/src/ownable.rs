@@ -1,11 +1,33 @@
 use frame_support::{
-    dispatch::{result::Result, DispatchError, DispatchResult},
-    traits::Get,
+    dispatch::{result::Result, DispatchError},
+    ensure,
+    StorageMap,
 };
 
-use sp_std::vec::Vec;
+use crate::{Domains, Error, Module, Trait};
 
 pub trait Ownable<AccountId> {
-    fn owner() -> AccountId;
-    fn set_owner(owner: &AccountId) -> AccountId;
+    type Id;
+
+    /// The current owner
+    fn owner(id: &Self::Id) -> Option<AccountId>;
+    /// Hand ownership to a new owner straight away, returning the previous owner
+    fn set_owner(id: &Self::Id, owner: &AccountId) -> Result<AccountId, DispatchError>;
+}
+
+impl<T: Trait> Ownable<T::AccountId> for Module<T> {
+    type Id = T::DomainId;
+
+    fn owner(domain_id: &T::DomainId) -> Option<T::AccountId> {
+        if Domains::<T>::contains_key(domain_id) {
+            Some(Domains::<T>::get(domain_id).owner)
+        } else {
+            None
+        }
+    }
+
+    fn set_owner(domain_id: &T::DomainId, owner: &T::AccountId) -> Result<T::AccountId, DispatchError> {
+        ensure!(Domains::<T>::contains_key(domain_id), Error::<T>::InvalidDomain);
+        Ok(Domains::<T>::mutate(domain_id, |domain| sp_std::mem::replace(&mut domain.owner, owner.clone())))
+    }
 }
@@ -1,13 +1,16 @@
-use crate::{Error, mock::*, RawEvent};
-use frame_support::{assert_ok, assert_noop};
+use crate::{DestroyWitness, Domains, Error, Role, Tokens, TransferPolicy, mock::*, RawEvent, item::Domain, ownable::Ownable};
+use frame_support::{assert_ok, assert_noop, StorageDoubleMap, StorageMap};
+use sp_runtime::Permill;
 
 const ALICE: u64 = 100;
 const BOB: u64 = 101;
+const CHARLIE: u64 = 102;
 const DOMAIN_ID: u64 = 0;
 const TICKER: &str = "ZOD";
 const NAME: &str = "Zodiac";
 const BASE_URI: &str = "https://barkingmad.io/assets";
 const NUM_TOKENS: u64 = 32;
+const NO_ROYALTY: Permill = Permill::from_percent(0);
 
 #[test]
 fn it_creates_a_domain() {
@@ -35,15 +38,15 @@ fn it_creates_a_token() {
 		// Event for domain creation with owner and domain id
 		assert_eq!(last_event(), Event::nft(RawEvent::DomainCreated(ALICE, 0)));
 		// Confirm we can't create a token without an amount
-		assert_noop!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, 0, BASE_URI.as_bytes().to_vec()), Error::<Test>::InvalidTotalSupply);		
+		assert_noop!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, 0, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None), Error::<Test>::InvalidTotalSupply);		
 		// Confirm we can't create a token without a valid base uri
-		assert_noop!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, "".as_bytes().to_vec()), Error::<Test>::InvalidBaseUri);		
-		// Confirm we can't create a token if not domain owner
-		assert_noop!(NftModule::create_token(Origin::signed(BOB), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec()), Error::<Test>::NotDomainOwner);		
+		assert_noop!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, "".as_bytes().to_vec(), NO_ROYALTY, None), Error::<Test>::InvalidBaseUri);		
+		// Confirm we can't create a token if not domain owner or minter
+		assert_noop!(NftModule::create_token(Origin::signed(BOB), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None), Error::<Test>::MissingRole);		
 		// Confirm we can't create a token if not a valid domain
-		assert_noop!(NftModule::create_token(Origin::signed(ALICE), 1, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec()), Error::<Test>::NotDomainOwner);		
+		assert_noop!(NftModule::create_token(Origin::signed(ALICE), 1, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None), Error::<Test>::InvalidDomain);		
 		// Create token for new domain id, creator being BOB with asset url and mint to BOB 32 tokens
-		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
 		// Check storage for token and supply is correct
 		assert_eq!(NftModule::tokens(DOMAIN_ID, 1).total_supply, NUM_TOKENS);
 		// Check Bob's balance, should be all of them
@@ -63,13 +66,13 @@ fn it_burns_tokens() {
 		// Event for domain creation with owner and domain id
 		assert_eq!(last_event(), Event::nft(RawEvent::DomainCreated(ALICE, DOMAIN_ID)));
 		// Create token for new domain id, creator being BOB with asset url and mint to BOB 32 tokens
-		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
 		// Event for token creation with id 1 minted 32 for BOB
 		assert_eq!(last_event(), Event::nft(RawEvent::TokenCreated(BOB, DOMAIN_ID, 1, NUM_TOKENS)));
 		// Burn 0 tokens from BOB by ALICE
 		assert_noop!(NftModule::burn_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 0), Error::<Test>::InvalidQuantityToBurn);
 		// BOB tries to Burn tokens from BOB
-		assert_noop!(NftModule::burn_tokens(Origin::signed(BOB), DOMAIN_ID, 1, BOB, NUM_TOKENS), Error::<Test>::NotDomainOwner);
+		assert_noop!(NftModule::burn_tokens(Origin::signed(BOB), DOMAIN_ID, 1, BOB, NUM_TOKENS), Error::<Test>::MissingRole);
 		// Burn NUM_TOKENS + 1 tokens from BOB by ALICE
 		assert_noop!(NftModule::burn_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, NUM_TOKENS + 1), Error::<Test>::BalanceOverflow);
 		// Burn 16 tokens from BOB by ALICE
@@ -80,3 +83,800 @@ fn it_burns_tokens() {
 		assert_eq!(last_event(), Event::nft(RawEvent::TokensBurnt(BOB, DOMAIN_ID, 1, NUM_TOKENS / 2)));
 	});
 }
+
+#[test]
+fn it_burns_own_tokens_and_tracks_supply() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" and a token capped at 40 minted to BOB
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, Some(40)));
+		// BOB can't burn more than BOB holds
+		assert_noop!(NftModule::burn_own_tokens(Origin::signed(BOB), DOMAIN_ID, 1, 0), Error::<Test>::InvalidQuantityToBurn);
+		assert_noop!(NftModule::burn_own_tokens(Origin::signed(BOB), DOMAIN_ID, 1, NUM_TOKENS + 1), Error::<Test>::InsufficientBalance);
+		assert_noop!(NftModule::burn_own_tokens(Origin::signed(CHARLIE), DOMAIN_ID, 1, 1), Error::<Test>::InsufficientBalance);
+		// BOB redeems 10 and ALICE burns 2 more of them
+		assert_ok!(NftModule::burn_own_tokens(Origin::signed(BOB), DOMAIN_ID, 1, 10));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensBurnt(BOB, DOMAIN_ID, 1, 10)));
+		assert_ok!(NftModule::burn_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 2));
+		assert_eq!(NftModule::balances(BOB, (DOMAIN_ID, 1)), NUM_TOKENS - 12);
+		assert_eq!(NftModule::tokens(DOMAIN_ID, 1).total_supply, NUM_TOKENS - 12);
+		assert_eq!(NftModule::tokens(DOMAIN_ID, 1).burned, 12);
+		assert_eq!(NftModule::burnt_items_for_domain(&DOMAIN_ID, &1), 12);
+		// Burned tokens still count towards the cap
+		assert_noop!(NftModule::mint_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 9), Error::<Test>::MaxSupplyExceeded);
+		assert_ok!(NftModule::mint_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 8));
+	});
+}
+
+#[test]
+fn it_transfers_tokens() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac"
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		// Create token for new domain id, creator being BOB with asset url and mint to BOB 32 tokens
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// Transfer 0 tokens from BOB to ALICE
+		assert_noop!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, ALICE, 0), Error::<Test>::InvalidQuantityToTransfer);
+		// Transfer NUM_TOKENS + 1 tokens from BOB to ALICE
+		assert_noop!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, ALICE, NUM_TOKENS + 1), Error::<Test>::InsufficientBalance);
+		// ALICE holds none of the tokens so can't transfer any
+		assert_noop!(NftModule::transfer(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 1), Error::<Test>::InsufficientBalance);
+		// Transfer 8 tokens from BOB to ALICE
+		assert_ok!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, ALICE, 8));
+		// Check balances have moved
+		assert_eq!(NftModule::balances(BOB, (DOMAIN_ID, 1)), NUM_TOKENS - 8);
+		assert_eq!(NftModule::balances(ALICE, (DOMAIN_ID, 1)), 8);
+		// Event for the transfer
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensTransferred(BOB, ALICE, DOMAIN_ID, 1, 8)));
+	});
+}
+
+#[test]
+fn it_transfers_tokens_in_a_batch() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" with two tokens both minted to BOB
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// An empty batch is rejected
+		assert_noop!(NftModule::transfer_batch(Origin::signed(BOB), vec![]), Error::<Test>::EmptyBatch);
+		// A batch where any transfer fails moves nothing
+		assert_noop!(NftModule::transfer_batch(Origin::signed(BOB), vec![(DOMAIN_ID, 1, ALICE, 4), (DOMAIN_ID, 2, ALICE, NUM_TOKENS + 1)]), Error::<Test>::InsufficientBalance);
+		assert_eq!(NftModule::balances(BOB, (DOMAIN_ID, 1)), NUM_TOKENS);
+		// Transfer from both tokens to ALICE and CHARLIE
+		let transfers = vec![(DOMAIN_ID, 1, ALICE, 4), (DOMAIN_ID, 2, ALICE, 2), (DOMAIN_ID, 2, CHARLIE, 6)];
+		assert_ok!(NftModule::transfer_batch(Origin::signed(BOB), transfers.clone()));
+		assert_eq!(NftModule::balances(BOB, (DOMAIN_ID, 1)), NUM_TOKENS - 4);
+		assert_eq!(NftModule::balances(BOB, (DOMAIN_ID, 2)), NUM_TOKENS - 8);
+		assert_eq!(NftModule::balances(ALICE, (DOMAIN_ID, 1)), 4);
+		assert_eq!(NftModule::balances(ALICE, (DOMAIN_ID, 2)), 2);
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 2)), 6);
+		// Single event for the whole batch
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensBatchTransferred(BOB, transfers)));
+	});
+}
+
+#[test]
+fn it_transfers_tokens_by_an_approved_operator() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" with two tokens both minted to BOB
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// CHARLIE is not approved to move BOB's tokens
+		assert_noop!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, CHARLIE, 1), Error::<Test>::NotApproved);
+		// BOB can't approve himself
+		assert_noop!(NftModule::set_approval_for_all(Origin::signed(BOB), DOMAIN_ID, BOB, true), Error::<Test>::InvalidOperator);
+		// BOB approves CHARLIE for token 1 only
+		assert_ok!(NftModule::set_approval_for_tokens(Origin::signed(BOB), DOMAIN_ID, vec![1], CHARLIE, true));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensApproved(BOB, CHARLIE, DOMAIN_ID, vec![1])));
+		assert_ok!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, ALICE, 2));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensTransferredByOperator(CHARLIE, BOB, ALICE, DOMAIN_ID, 1, 2)));
+		assert_eq!(NftModule::balances(ALICE, (DOMAIN_ID, 1)), 2);
+		assert_noop!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 2, BOB, ALICE, 2), Error::<Test>::NotApproved);
+		// BOB approves CHARLIE for the whole domain
+		assert_ok!(NftModule::set_approval_for_all(Origin::signed(BOB), DOMAIN_ID, CHARLIE, true));
+		assert_eq!(last_event(), Event::nft(RawEvent::OperatorApproved(BOB, CHARLIE, DOMAIN_ID)));
+		assert_ok!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 2, BOB, ALICE, 2));
+		assert_eq!(NftModule::balances(ALICE, (DOMAIN_ID, 2)), 2);
+		// Revoking both approvals stops CHARLIE again
+		assert_ok!(NftModule::set_approval_for_all(Origin::signed(BOB), DOMAIN_ID, CHARLIE, false));
+		assert_eq!(last_event(), Event::nft(RawEvent::OperatorRevoked(BOB, CHARLIE, DOMAIN_ID)));
+		assert_ok!(NftModule::set_approval_for_tokens(Origin::signed(BOB), DOMAIN_ID, vec![1], CHARLIE, false));
+		assert_noop!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, ALICE, 1), Error::<Test>::NotApproved);
+	});
+}
+
+#[test]
+fn it_spends_allowances_until_they_expire() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" and mint NUM_TOKENS to BOB
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// An allowance can't expire in the past
+		assert_noop!(NftModule::approve(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 5, Some(1)), Error::<Test>::InvalidExpiry);
+		// BOB allows CHARLIE to move 5 tokens until block 10
+		assert_ok!(NftModule::approve(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 5, Some(10)));
+		assert_eq!(last_event(), Event::nft(RawEvent::AllowanceSet(BOB, CHARLIE, DOMAIN_ID, 1, 5, Some(10))));
+		assert_ok!(NftModule::increase_allowance(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 3));
+		assert_ok!(NftModule::decrease_allowance(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 2));
+		assert_eq!(NftModule::allowance(&BOB, &CHARLIE, DOMAIN_ID, 1), 6);
+		// CHARLIE can't spend more than allowed
+		assert_noop!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, CHARLIE, 7), Error::<Test>::InsufficientAllowance);
+		// Spending decrements the allowance
+		assert_ok!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, CHARLIE, 4));
+		assert_eq!(NftModule::allowance(&BOB, &CHARLIE, DOMAIN_ID, 1), 2);
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 1)), 4);
+		// Once expired the remainder can't be spent
+		run_to_block(10);
+		assert_eq!(NftModule::allowance(&BOB, &CHARLIE, DOMAIN_ID, 1), 0);
+		assert_noop!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, CHARLIE, 1), Error::<Test>::AllowanceExpired);
+		// but BOB can still clear it
+		assert_ok!(NftModule::decrease_allowance(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 2));
+		assert!(NftModule::allowances((DOMAIN_ID, 1), (BOB, CHARLIE)).is_none());
+	});
+}
+
+#[test]
+fn it_manages_items_through_the_domain_trait() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" and an item minted to BOB without going through extrinsics
+		let domain_id = <NftModule as Domain<u64>>::create_domain(&ALICE, TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()).unwrap();
+		// Minting goes through the same role check as create_token
+		assert_noop!(NftModule::mint_item(&domain_id, &BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec()), Error::<Test>::MissingRole);
+		assert_ok!(NftModule::grant_role(Origin::signed(ALICE), domain_id, BOB, Role::Minter));
+		let item_id = NftModule::mint_item(&domain_id, &BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec()).unwrap();
+		assert_eq!(NftModule::total_items_for_domain(&domain_id, &item_id), NUM_TOKENS);
+		assert_eq!(NftModule::owner_of_item(&domain_id, &item_id), Some(BOB));
+		assert_eq!(NftModule::data_for_item(&domain_id, &item_id), Some(BASE_URI.as_bytes().to_vec()));
+		assert_eq!(NftModule::owner_of_item(&domain_id, &(item_id + 1)), None);
+		// Transfer, approve and burn through the trait
+		assert_ok!(NftModule::transfer_item(&domain_id, &item_id, &BOB, &ALICE, 2));
+		assert_noop!(NftModule::transfer_multiple_items(&domain_id, &BOB, vec![item_id, item_id], vec![1, NUM_TOKENS], vec![ALICE, CHARLIE]), Error::<Test>::InsufficientBalance);
+		assert_ok!(NftModule::transfer_multiple_items(&domain_id, &BOB, vec![item_id, item_id], vec![1, 3], vec![ALICE, CHARLIE]));
+		assert_eq!(NftModule::balance_for_user(&domain_id, &item_id, &ALICE), 3);
+		assert_eq!(NftModule::balance_for_user(&domain_id, &item_id, &CHARLIE), 3);
+		assert_noop!(NftModule::approval_for_item(&domain_id, &(item_id + 1), &BOB, &CHARLIE), Error::<Test>::InvalidToken);
+		assert_ok!(NftModule::approval_for_item(&domain_id, &item_id, &BOB, &CHARLIE));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensApproved(BOB, CHARLIE, domain_id, vec![item_id])));
+		assert!(NftModule::is_approved(&BOB, &CHARLIE, domain_id, item_id));
+		assert_ok!(NftModule::burn_items(&domain_id, &item_id, &BOB, 6));
+		assert_eq!(NftModule::balance_for_user(&domain_id, &item_id, &BOB), NUM_TOKENS - 12);
+	});
+}
+
+#[test]
+fn it_sells_listed_tokens_at_a_fixed_price() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" and mint NUM_TOKENS to BOB
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// BOB can't list more than BOB holds or for nothing
+		assert_noop!(NftModule::list_tokens(Origin::signed(BOB), DOMAIN_ID, 1, NUM_TOKENS + 1, 100), Error::<Test>::InsufficientBalance);
+		assert_noop!(NftModule::list_tokens(Origin::signed(BOB), DOMAIN_ID, 1, 10, 0), Error::<Test>::InvalidPrice);
+		// BOB lists 10 tokens at 100 each
+		assert_ok!(NftModule::list_tokens(Origin::signed(BOB), DOMAIN_ID, 1, 10, 100));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensListed(BOB, DOMAIN_ID, 1, 10, 100)));
+		// Listed tokens are locked so they can't be moved or double sold
+		assert_noop!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, NUM_TOKENS - 9), Error::<Test>::InsufficientBalance);
+		assert_noop!(NftModule::buy_tokens(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, 11), Error::<Test>::InsufficientListingQuantity);
+		assert_noop!(NftModule::buy_tokens(Origin::signed(BOB), DOMAIN_ID, 1, BOB, 1), Error::<Test>::CannotBuyOwnListing);
+		// CHARLIE buys 4, paying for them in the same call
+		assert_ok!(NftModule::buy_tokens(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, 4));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensSold(BOB, CHARLIE, DOMAIN_ID, 1, 4, 400)));
+		assert_eq!(Balances::free_balance(CHARLIE), 10_000 - 400);
+		assert_eq!(Balances::free_balance(BOB), 10_000 + 400);
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 1)), 4);
+		assert_eq!(NftModule::locked(BOB, (DOMAIN_ID, 1)), 6);
+		// A buyer who can't pay gets nothing
+		assert!(NftModule::buy_tokens(Origin::signed(200), DOMAIN_ID, 1, BOB, 6).is_err());
+		assert_eq!(NftModule::balances(200, (DOMAIN_ID, 1)), 0);
+		// Cancelling unlocks the rest
+		assert_ok!(NftModule::cancel_listing(Origin::signed(BOB), DOMAIN_ID, 1));
+		assert_eq!(NftModule::locked(BOB, (DOMAIN_ID, 1)), 0);
+		assert_noop!(NftModule::buy_tokens(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, 1), Error::<Test>::ListingNotFound);
+	});
+}
+
+#[test]
+fn it_keeps_a_book_of_bids() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac", mint NUM_TOKENS to BOB and list 10 at 100 each
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_ok!(NftModule::list_tokens(Origin::signed(BOB), DOMAIN_ID, 1, 10, 100));
+		// A bid at the listing price fills straight away
+		assert_ok!(NftModule::place_bid(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, 2, 150));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensSold(BOB, CHARLIE, DOMAIN_ID, 1, 2, 200)));
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 1)), 2);
+		// Lower bids go on the book with the offer reserved
+		assert_ok!(NftModule::place_bid(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, 3, 80));
+		assert_eq!(last_event(), Event::nft(RawEvent::BidPlaced(0, CHARLIE, BOB, DOMAIN_ID, 1, 3, 80)));
+		assert_ok!(NftModule::place_bid(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 1, 50));
+		assert_ok!(NftModule::place_bid(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 1, 60));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 240);
+		assert_eq!(Balances::reserved_balance(ALICE), 110);
+		assert_eq!(NftModule::bids_for_token(DOMAIN_ID, 1).len(), 3);
+		// Only the seller can accept or reject, only the bidder can cancel
+		assert_noop!(NftModule::accept_bid(Origin::signed(CHARLIE), DOMAIN_ID, 1, 0), Error::<Test>::NotBidSeller);
+		assert_noop!(NftModule::cancel_bid(Origin::signed(BOB), DOMAIN_ID, 1, 0), Error::<Test>::NotBidder);
+		// BOB accepts CHARLIE's bid
+		assert_ok!(NftModule::accept_bid(Origin::signed(BOB), DOMAIN_ID, 1, 0));
+		assert_eq!(last_event(), Event::nft(RawEvent::BidAccepted(0, BOB, CHARLIE, DOMAIN_ID, 1, 3, 240)));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), 10_000 - 200 - 240);
+		assert_eq!(Balances::free_balance(BOB), 10_000 + 200 + 240);
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 1)), 5);
+		assert_eq!(NftModule::listings((DOMAIN_ID, 1), BOB).unwrap().quantity, 5);
+		// BOB rejects one of ALICE's bids and ALICE cancels the other
+		assert_ok!(NftModule::reject_bid(Origin::signed(BOB), DOMAIN_ID, 1, 1));
+		assert_eq!(last_event(), Event::nft(RawEvent::BidRejected(1, DOMAIN_ID, 1)));
+		assert_ok!(NftModule::cancel_bid(Origin::signed(ALICE), DOMAIN_ID, 1, 2));
+		assert_eq!(last_event(), Event::nft(RawEvent::BidCancelled(2, DOMAIN_ID, 1)));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert!(NftModule::bids_for_token(DOMAIN_ID, 1).is_empty());
+	});
+}
+
+#[test]
+fn it_settles_english_auctions() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" and mint NUM_TOKENS to BOB
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// Auctions must end in the future
+		assert_noop!(NftModule::create_auction(Origin::signed(BOB), DOMAIN_ID, 1, 1, 100, 10, 1), Error::<Test>::InvalidAuctionEnd);
+		// BOB auctions 1 token with a reserve of 100 ending at block 20
+		assert_ok!(NftModule::create_auction(Origin::signed(BOB), DOMAIN_ID, 1, 1, 100, 10, 20));
+		assert_eq!(last_event(), Event::nft(RawEvent::AuctionCreated(0, BOB, DOMAIN_ID, 1, 1, 100, 20)));
+		assert_eq!(NftModule::locked(BOB, (DOMAIN_ID, 1)), 1);
+		// Bids must meet the reserve and then the minimum increment
+		assert_noop!(NftModule::bid_on_auction(Origin::signed(CHARLIE), 0, 99), Error::<Test>::BidTooLow);
+		assert_noop!(NftModule::bid_on_auction(Origin::signed(BOB), 0, 100), Error::<Test>::CannotBidOnOwnAuction);
+		assert_ok!(NftModule::bid_on_auction(Origin::signed(CHARLIE), 0, 100));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 100);
+		assert_noop!(NftModule::bid_on_auction(Origin::signed(ALICE), 0, 105), Error::<Test>::BidTooLow);
+		// Being outbid releases the previous bidder's reserve
+		assert_ok!(NftModule::bid_on_auction(Origin::signed(ALICE), 0, 110));
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 110);
+		// The seller can't pull an auction once it has bids
+		assert_noop!(NftModule::cancel_auction(Origin::signed(BOB), 0), Error::<Test>::AuctionHasBids);
+		// A bid in the last blocks extends the end
+		run_to_block(17);
+		assert_ok!(NftModule::bid_on_auction(Origin::signed(CHARLIE), 0, 130));
+		assert_eq!(NftModule::auctions(0).unwrap().end, 22);
+		run_to_block(20);
+		assert!(NftModule::auctions(0).is_some());
+		// Settled at the new end, token to CHARLIE and currency to BOB
+		run_to_block(22);
+		assert!(NftModule::auctions(0).is_none());
+		assert_eq!(last_event(), Event::nft(RawEvent::AuctionSettled(0, Some(CHARLIE), 130)));
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 1)), 1);
+		assert_eq!(NftModule::locked(BOB, (DOMAIN_ID, 1)), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), 10_000 - 130);
+		assert_eq!(Balances::free_balance(BOB), 10_000 + 130);
+		assert_eq!(Balances::free_balance(ALICE), 10_000);
+	});
+}
+
+#[test]
+fn it_carries_auctions_over_when_too_many_end_together() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// Only two auctions may be created to end in the same block
+		assert_ok!(NftModule::create_auction(Origin::signed(BOB), DOMAIN_ID, 1, 1, 100, 10, 10));
+		assert_ok!(NftModule::create_auction(Origin::signed(BOB), DOMAIN_ID, 1, 1, 100, 10, 10));
+		assert_noop!(NftModule::create_auction(Origin::signed(BOB), DOMAIN_ID, 1, 1, 100, 10, 10), Error::<Test>::TooManyAuctionsEnding);
+		// A late bid pushes a third auction into the same block
+		assert_ok!(NftModule::create_auction(Origin::signed(BOB), DOMAIN_ID, 1, 1, 100, 10, 8));
+		run_to_block(5);
+		assert_ok!(NftModule::bid_on_auction(Origin::signed(CHARLIE), 2, 100));
+		assert_eq!(NftModule::auction_endings(10).len(), 3);
+		// Two settle at block 10 and the last one the block after
+		run_to_block(10);
+		assert_eq!(NftModule::auction_endings(11).len(), 1);
+		run_to_block(11);
+		assert!(NftModule::auctions(0).is_none() && NftModule::auctions(1).is_none() && NftModule::auctions(2).is_none());
+		assert_eq!(NftModule::locked(BOB, (DOMAIN_ID, 1)), 0);
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 1)), 1);
+	});
+}
+
+#[test]
+fn it_sells_through_a_dutch_auction() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" and mint NUM_TOKENS to ALICE
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, ALICE, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// Only the domain owner runs primary sales
+		assert_noop!(NftModule::create_dutch_auction(Origin::signed(BOB), DOMAIN_ID, 1, 5, 1000, 400, 100), Error::<Test>::NotDomainOwner);
+		assert_noop!(NftModule::create_dutch_auction(Origin::signed(ALICE), DOMAIN_ID, 1, 5, 300, 400, 100), Error::<Test>::InvalidPrice);
+		// ALICE sells 5 tokens starting at 1000 and dropping 100 a block to 400
+		assert_ok!(NftModule::create_dutch_auction(Origin::signed(ALICE), DOMAIN_ID, 1, 5, 1000, 400, 100));
+		assert_eq!(last_event(), Event::nft(RawEvent::DutchAuctionCreated(0, ALICE, DOMAIN_ID, 1, 5, 1000, 400, 100)));
+		assert_ok!(NftModule::buy_from_dutch_auction(Origin::signed(BOB), 0, 1, 1000));
+		assert_eq!(last_event(), Event::nft(RawEvent::DutchAuctionPurchase(0, BOB, 1, 1000)));
+		// Three blocks later the price has dropped to 700
+		run_to_block(4);
+		assert_noop!(NftModule::buy_from_dutch_auction(Origin::signed(CHARLIE), 0, 2, 600), Error::<Test>::PriceAboveLimit);
+		assert_ok!(NftModule::buy_from_dutch_auction(Origin::signed(CHARLIE), 0, 2, 700));
+		assert_eq!(last_event(), Event::nft(RawEvent::DutchAuctionPurchase(0, CHARLIE, 2, 700)));
+		// The price never drops below the floor
+		run_to_block(20);
+		assert_noop!(NftModule::buy_from_dutch_auction(Origin::signed(BOB), 0, 3, 400), Error::<Test>::InsufficientListingQuantity);
+		assert_ok!(NftModule::buy_from_dutch_auction(Origin::signed(BOB), 0, 2, 400));
+		assert_eq!(last_event(), Event::nft(RawEvent::DutchAuctionPurchase(0, BOB, 2, 400)));
+		// Sold out auctions are removed
+		assert!(NftModule::dutch_auctions(0).is_none());
+		assert_eq!(NftModule::balances(BOB, (DOMAIN_ID, 1)), 3);
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 1)), 2);
+		assert_eq!(NftModule::locked(ALICE, (DOMAIN_ID, 1)), 0);
+		assert_eq!(Balances::free_balance(BOB), 10_000 - 1000 - 800);
+		assert_eq!(Balances::free_balance(CHARLIE), 10_000 - 1400);
+		assert_eq!(Balances::free_balance(ALICE), 10_000 + 3200);
+	});
+}
+
+#[test]
+fn it_pays_creator_royalties_on_sales() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" and a token created by CHARLIE with a 10% royalty
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, CHARLIE, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), Permill::from_percent(10), None));
+		assert_eq!(NftModule::royalty_info(DOMAIN_ID, 1, 1000), Some((CHARLIE, 100)));
+		assert_eq!(NftModule::royalty_info(DOMAIN_ID, 2, 1000), None);
+		// Only the creator may change the royalty, and only downwards
+		assert_noop!(NftModule::set_royalty(Origin::signed(ALICE), DOMAIN_ID, 1, Permill::from_percent(5)), Error::<Test>::NotTokenCreator);
+		assert_noop!(NftModule::set_royalty(Origin::signed(CHARLIE), DOMAIN_ID, 1, Permill::from_percent(20)), Error::<Test>::RoyaltyIncrease);
+		assert_ok!(NftModule::set_royalty(Origin::signed(CHARLIE), DOMAIN_ID, 1, Permill::from_percent(5)));
+		assert_eq!(last_event(), Event::nft(RawEvent::RoyaltySet(DOMAIN_ID, 1, Permill::from_percent(5))));
+		// No royalty is taken when the creator sells
+		assert_ok!(NftModule::list_tokens(Origin::signed(CHARLIE), DOMAIN_ID, 1, 10, 100));
+		assert_ok!(NftModule::buy_tokens(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 10));
+		assert_eq!(Balances::free_balance(CHARLIE), 10_000 + 1000);
+		// BOB resells 4 to ALICE and CHARLIE gets 5% of the proceeds
+		assert_ok!(NftModule::list_tokens(Origin::signed(BOB), DOMAIN_ID, 1, 10, 100));
+		assert_ok!(NftModule::buy_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 4));
+		assert_eq!(Balances::free_balance(ALICE), 10_000 - 400);
+		assert_eq!(Balances::free_balance(BOB), 10_000 - 1000 + 380);
+		assert_eq!(Balances::free_balance(CHARLIE), 10_000 + 1000 + 20);
+		// Accepted bids pay the royalty out of the bidder's reserve
+		assert_ok!(NftModule::place_bid(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 2, 50));
+		assert_ok!(NftModule::accept_bid(Origin::signed(BOB), DOMAIN_ID, 1, 0));
+		assert_eq!(Balances::free_balance(ALICE), 10_000 - 400 - 100);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(BOB), 10_000 - 1000 + 380 + 95);
+		assert_eq!(Balances::free_balance(CHARLIE), 10_000 + 1000 + 20 + 5);
+	});
+}
+
+#[test]
+fn it_transfers_domain_ownership_in_two_steps() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" owned by ALICE
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		// Only the owner can propose a new owner
+		assert_noop!(NftModule::transfer_domain_ownership(Origin::signed(BOB), DOMAIN_ID, BOB), Error::<Test>::NotDomainOwner);
+		assert_noop!(NftModule::cancel_domain_ownership_transfer(Origin::signed(ALICE), DOMAIN_ID), Error::<Test>::NoPendingOwner);
+		// ALICE proposes CHARLIE then cancels
+		assert_ok!(NftModule::transfer_domain_ownership(Origin::signed(ALICE), DOMAIN_ID, CHARLIE));
+		assert_eq!(last_event(), Event::nft(RawEvent::DomainOwnershipProposed(DOMAIN_ID, ALICE, CHARLIE)));
+		assert_ok!(NftModule::cancel_domain_ownership_transfer(Origin::signed(ALICE), DOMAIN_ID));
+		assert_eq!(last_event(), Event::nft(RawEvent::DomainOwnershipTransferCancelled(DOMAIN_ID)));
+		assert_noop!(NftModule::accept_domain_ownership(Origin::signed(CHARLIE), DOMAIN_ID, 0), Error::<Test>::NotPendingOwner);
+		// ALICE proposes BOB, who has to accept before anything changes
+		assert_ok!(NftModule::grant_role(Origin::signed(ALICE), DOMAIN_ID, CHARLIE, Role::Minter));
+		assert_ok!(NftModule::transfer_domain_ownership(Origin::signed(ALICE), DOMAIN_ID, BOB));
+		assert_eq!(NftModule::owner(&DOMAIN_ID), Some(ALICE));
+		assert_noop!(NftModule::accept_domain_ownership(Origin::signed(CHARLIE), DOMAIN_ID, 0), Error::<Test>::NotPendingOwner);
+		// The witness has to cover every role granted on the domain
+		assert_noop!(NftModule::accept_domain_ownership(Origin::signed(BOB), DOMAIN_ID, 0), Error::<Test>::BadWitness);
+		assert_ok!(NftModule::accept_domain_ownership(Origin::signed(BOB), DOMAIN_ID, 1));
+		assert_eq!(last_event(), Event::nft(RawEvent::DomainOwnershipTransferred(DOMAIN_ID, ALICE, BOB)));
+		assert_eq!(NftModule::owner(&DOMAIN_ID), Some(BOB));
+		assert_eq!(NftModule::pending_domain_owners(DOMAIN_ID), None);
+		// BOB now controls the domain, ALICE doesn't and roles ALICE granted are gone
+		assert_noop!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, ALICE, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None), Error::<Test>::MissingRole);
+		assert!(!NftModule::roles(DOMAIN_ID, (CHARLIE, Role::Minter)));
+		assert_noop!(NftModule::create_token(Origin::signed(CHARLIE), DOMAIN_ID, CHARLIE, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None), Error::<Test>::MissingRole);
+		assert_ok!(NftModule::create_token(Origin::signed(BOB), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+	});
+}
+
+#[test]
+fn it_grants_and_revokes_domain_roles() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" owned by ALICE
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		// Only the owner can grant admin and only admins can grant other roles
+		assert_noop!(NftModule::grant_role(Origin::signed(BOB), DOMAIN_ID, BOB, Role::Admin), Error::<Test>::NotDomainOwner);
+		assert_noop!(NftModule::grant_role(Origin::signed(BOB), DOMAIN_ID, BOB, Role::Minter), Error::<Test>::MissingRole);
+		assert_noop!(NftModule::grant_role(Origin::signed(ALICE), 1, BOB, Role::Minter), Error::<Test>::InvalidDomain);
+		// ALICE makes BOB an admin, who makes CHARLIE a minter
+		assert_ok!(NftModule::grant_role(Origin::signed(ALICE), DOMAIN_ID, BOB, Role::Admin));
+		assert_eq!(last_event(), Event::nft(RawEvent::RoleGranted(DOMAIN_ID, BOB, Role::Admin)));
+		assert_noop!(NftModule::grant_role(Origin::signed(BOB), DOMAIN_ID, CHARLIE, Role::Admin), Error::<Test>::NotDomainOwner);
+		assert_ok!(NftModule::grant_role(Origin::signed(BOB), DOMAIN_ID, CHARLIE, Role::Minter));
+		// CHARLIE can mint but not burn
+		assert_ok!(NftModule::create_token(Origin::signed(CHARLIE), DOMAIN_ID, CHARLIE, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_noop!(NftModule::burn_tokens(Origin::signed(CHARLIE), DOMAIN_ID, 1, CHARLIE, 1), Error::<Test>::MissingRole);
+		// BOB holds every role through admin
+		assert_ok!(NftModule::burn_tokens(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 1));
+		// Revoking the minter role takes effect immediately
+		assert_ok!(NftModule::revoke_role(Origin::signed(BOB), DOMAIN_ID, CHARLIE, Role::Minter));
+		assert_eq!(last_event(), Event::nft(RawEvent::RoleRevoked(DOMAIN_ID, CHARLIE, Role::Minter)));
+		assert_noop!(NftModule::create_token(Origin::signed(CHARLIE), DOMAIN_ID, CHARLIE, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None), Error::<Test>::MissingRole);
+		assert_ok!(NftModule::revoke_role(Origin::signed(ALICE), DOMAIN_ID, BOB, Role::Admin));
+		assert!(!NftModule::has_role(DOMAIN_ID, &BOB, Role::Burner));
+	});
+}
+
+#[test]
+fn it_mints_additional_supply_up_to_the_cap() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" owned by ALICE and a token capped at 40
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_noop!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, Some(NUM_TOKENS - 1)), Error::<Test>::InvalidMaxSupply);
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, Some(40)));
+		// Only minters can mint
+		assert_noop!(NftModule::mint_tokens(Origin::signed(BOB), DOMAIN_ID, 1, BOB, 1), Error::<Test>::MissingRole);
+		assert_noop!(NftModule::mint_tokens(Origin::signed(ALICE), DOMAIN_ID, 2, BOB, 1), Error::<Test>::InvalidToken);
+		assert_noop!(NftModule::mint_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 0), Error::<Test>::InvalidQuantityToMint);
+		// ALICE mints 5 more to CHARLIE
+		assert_ok!(NftModule::mint_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, CHARLIE, 5));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokensMinted(CHARLIE, DOMAIN_ID, 1, 5)));
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 1)), 5);
+		assert_eq!(NftModule::tokens(DOMAIN_ID, 1).total_supply, NUM_TOKENS + 5);
+		// The cap can't be passed
+		assert_noop!(NftModule::mint_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, CHARLIE, 4), Error::<Test>::MaxSupplyExceeded);
+		// The cap can be lowered but never raised or set below the supply
+		assert_noop!(NftModule::set_max_supply(Origin::signed(ALICE), DOMAIN_ID, 1, 41), Error::<Test>::InvalidMaxSupply);
+		assert_noop!(NftModule::set_max_supply(Origin::signed(ALICE), DOMAIN_ID, 1, NUM_TOKENS), Error::<Test>::InvalidMaxSupply);
+		assert_ok!(NftModule::set_max_supply(Origin::signed(ALICE), DOMAIN_ID, 1, 38));
+		assert_eq!(last_event(), Event::nft(RawEvent::MaxSupplySet(DOMAIN_ID, 1, 38)));
+		// Freezing stops any further minting
+		assert_ok!(NftModule::freeze_supply(Origin::signed(ALICE), DOMAIN_ID, 1));
+		assert_eq!(NftModule::tokens(DOMAIN_ID, 1).max_supply, Some(NUM_TOKENS + 5));
+		assert_noop!(NftModule::mint_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, CHARLIE, 1), Error::<Test>::MaxSupplyExceeded);
+	});
+}
+
+#[test]
+fn it_enforces_transfer_policy_and_freezes() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" and a token minted to ALICE
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, ALICE, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_ok!(NftModule::transfer(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, 10));
+		// Only admins can change the policy
+		assert_noop!(NftModule::set_transfer_policy(Origin::signed(BOB), DOMAIN_ID, 1, TransferPolicy::Soulbound), Error::<Test>::MissingRole);
+		// Soulbound tokens can't move or be put up for sale
+		assert_ok!(NftModule::set_transfer_policy(Origin::signed(ALICE), DOMAIN_ID, 1, TransferPolicy::Soulbound));
+		assert_eq!(last_event(), Event::nft(RawEvent::TransferPolicySet(DOMAIN_ID, 1, TransferPolicy::Soulbound)));
+		assert_noop!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 1), Error::<Test>::TokenNotTransferable);
+		assert_noop!(NftModule::transfer(Origin::signed(ALICE), DOMAIN_ID, 1, CHARLIE, 1), Error::<Test>::TokenNotTransferable);
+		assert_noop!(NftModule::list_tokens(Origin::signed(BOB), DOMAIN_ID, 1, 1, 100), Error::<Test>::TokenNotTransferable);
+		// Owner only tokens can be handed out by ALICE but not passed on
+		assert_ok!(NftModule::set_transfer_policy(Origin::signed(ALICE), DOMAIN_ID, 1, TransferPolicy::DomainOwnerOnly));
+		assert_ok!(NftModule::transfer(Origin::signed(ALICE), DOMAIN_ID, 1, CHARLIE, 1));
+		assert_noop!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 1), Error::<Test>::TransferRestricted);
+		// ALICE can move them for holders, other operators can't
+		assert_ok!(NftModule::set_approval_for_all(Origin::signed(BOB), DOMAIN_ID, ALICE, true));
+		assert_ok!(NftModule::set_approval_for_all(Origin::signed(BOB), DOMAIN_ID, CHARLIE, true));
+		assert_ok!(NftModule::transfer_from(Origin::signed(ALICE), DOMAIN_ID, 1, BOB, CHARLIE, 1));
+		assert_noop!(NftModule::transfer_from(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, CHARLIE, 1), Error::<Test>::TransferRestricted);
+		// Frozen holders can't move transferable tokens until thawed
+		assert_ok!(NftModule::set_transfer_policy(Origin::signed(ALICE), DOMAIN_ID, 1, TransferPolicy::Transferable));
+		assert_noop!(NftModule::freeze(Origin::signed(BOB), DOMAIN_ID, 1, BOB), Error::<Test>::MissingRole);
+		assert_ok!(NftModule::freeze(Origin::signed(ALICE), DOMAIN_ID, 1, BOB));
+		assert_eq!(last_event(), Event::nft(RawEvent::AccountFrozen(BOB, DOMAIN_ID, 1)));
+		assert_noop!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 1), Error::<Test>::AccountFrozen);
+		assert_noop!(NftModule::transfer_batch(Origin::signed(BOB), vec![(DOMAIN_ID, 1, CHARLIE, 1)]), Error::<Test>::AccountFrozen);
+		assert_ok!(NftModule::transfer(Origin::signed(CHARLIE), DOMAIN_ID, 1, BOB, 1));
+		assert_ok!(NftModule::thaw(Origin::signed(ALICE), DOMAIN_ID, 1, BOB));
+		assert_eq!(last_event(), Event::nft(RawEvent::AccountThawed(BOB, DOMAIN_ID, 1)));
+		assert_ok!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 1));
+	});
+}
+
+#[test]
+fn it_sets_and_clears_attributes_with_deposits() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" owned by ALICE and a token
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		// Only metadata editors can set attributes, within the limits
+		set_deposits(0, 0, 1);
+		assert_noop!(NftModule::set_attribute(Origin::signed(BOB), DOMAIN_ID, Some(1), b"rarity".to_vec(), b"rare".to_vec()), Error::<Test>::MissingRole);
+		assert_noop!(NftModule::set_attribute(Origin::signed(ALICE), DOMAIN_ID, Some(2), b"rarity".to_vec(), b"rare".to_vec()), Error::<Test>::InvalidToken);
+		assert_noop!(NftModule::set_attribute(Origin::signed(ALICE), DOMAIN_ID, Some(1), vec![0; 17], b"rare".to_vec()), Error::<Test>::KeyTooLong);
+		assert_noop!(NftModule::set_attribute(Origin::signed(ALICE), DOMAIN_ID, Some(1), b"rarity".to_vec(), vec![0; 33]), Error::<Test>::ValueTooLong);
+		// CHARLIE edits metadata and ALICE holds the deposit of 10 + 1 per byte
+		assert_ok!(NftModule::grant_role(Origin::signed(ALICE), DOMAIN_ID, CHARLIE, Role::MetadataEditor));
+		assert_ok!(NftModule::set_attribute(Origin::signed(CHARLIE), DOMAIN_ID, Some(1), b"rarity".to_vec(), b"rare".to_vec()));
+		assert_eq!(last_event(), Event::nft(RawEvent::AttributeSet(DOMAIN_ID, Some(1), b"rarity".to_vec(), b"rare".to_vec())));
+		assert_ok!(NftModule::set_attribute(Origin::signed(CHARLIE), DOMAIN_ID, None, b"theme".to_vec(), b"stars".to_vec()));
+		assert_eq!(Balances::reserved_balance(ALICE), 20 + 20);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(NftModule::attributes_of(DOMAIN_ID, Some(1)), vec![(b"rarity".to_vec(), b"rare".to_vec())]);
+		assert_eq!(NftModule::attributes_of(DOMAIN_ID, None), vec![(b"theme".to_vec(), b"stars".to_vec())]);
+		// Overwriting with a longer value tops up the deposit
+		assert_ok!(NftModule::set_attribute(Origin::signed(CHARLIE), DOMAIN_ID, Some(1), b"rarity".to_vec(), b"legendary".to_vec()));
+		assert_eq!(Balances::reserved_balance(ALICE), 25 + 20);
+		// Clearing refunds it
+		assert_noop!(NftModule::clear_attribute(Origin::signed(CHARLIE), DOMAIN_ID, Some(1), b"level".to_vec()), Error::<Test>::AttributeNotFound);
+		assert_ok!(NftModule::clear_attribute(Origin::signed(CHARLIE), DOMAIN_ID, Some(1), b"rarity".to_vec()));
+		assert_eq!(last_event(), Event::nft(RawEvent::AttributeCleared(DOMAIN_ID, Some(1), b"rarity".to_vec())));
+		assert_eq!(Balances::reserved_balance(ALICE), 20);
+		assert!(NftModule::attributes_of(DOMAIN_ID, Some(1)).is_empty());
+		// A new owner takes over the remaining deposit
+		assert_ok!(NftModule::transfer_domain_ownership(Origin::signed(ALICE), DOMAIN_ID, BOB));
+		assert_ok!(NftModule::accept_domain_ownership(Origin::signed(BOB), DOMAIN_ID, 1));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 20);
+	});
+}
+
+#[test]
+fn it_resolves_token_uris() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" owned by ALICE and a token
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, b"https://zod.io".to_vec(), NO_ROYALTY, None));
+		assert_eq!(NftModule::token_uri(DOMAIN_ID, 2), None);
+		// Without a template the base uri and id are joined by a slash
+		assert_eq!(NftModule::token_uri(DOMAIN_ID, 1), Some(b"https://zod.io/1".to_vec()));
+		// Only metadata editors can change the template
+		assert_noop!(NftModule::set_uri_template(Origin::signed(BOB), DOMAIN_ID, Some(b"{base}/{id}.json".to_vec())), Error::<Test>::MissingRole);
+		assert_ok!(NftModule::set_uri_template(Origin::signed(ALICE), DOMAIN_ID, Some(b"{base}/tokens/{id}.json".to_vec())));
+		assert_eq!(last_event(), Event::nft(RawEvent::UriTemplateSet(DOMAIN_ID, Some(b"{base}/tokens/{id}.json".to_vec()))));
+		assert_eq!(NftModule::token_uri(DOMAIN_ID, 1), Some(b"https://zod.io/tokens/1.json".to_vec()));
+		// A token uri overrides the template until cleared
+		assert_noop!(NftModule::set_token_uri(Origin::signed(ALICE), DOMAIN_ID, 2, Some(b"ipfs://abc".to_vec())), Error::<Test>::InvalidToken);
+		assert_ok!(NftModule::set_token_uri(Origin::signed(ALICE), DOMAIN_ID, 1, Some(b"ipfs://abc".to_vec())));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokenUriSet(DOMAIN_ID, 1, Some(b"ipfs://abc".to_vec()))));
+		assert_eq!(NftModule::token_uri(DOMAIN_ID, 1), Some(b"ipfs://abc".to_vec()));
+		assert_ok!(NftModule::set_token_uri(Origin::signed(ALICE), DOMAIN_ID, 1, None));
+		assert_eq!(NftModule::token_uri(DOMAIN_ID, 1), Some(b"https://zod.io/tokens/1.json".to_vec()));
+	});
+}
+
+#[test]
+fn it_reserves_deposits_for_domains_and_tokens() {
+	new_test_ext().execute_with(|| {
+		// Domains cost 100, tokens 10 and every stored byte 1
+		set_deposits(100, 10, 1);
+		// Create domain ZOD "Zodiac" owned by ALICE, holding 100 + 3 + 6
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_eq!(Balances::reserved_balance(ALICE), 109);
+		// A token adds 10 + 28 for the base uri
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_eq!(NftModule::deposits(DOMAIN_ID, Some(1)), 38);
+		assert_eq!(Balances::reserved_balance(ALICE), 109 + 38);
+		assert_eq!(NftModule::domain_deposits(DOMAIN_ID), 109 + 38);
+		// Metadata grows and shrinks the deposit with it
+		assert_ok!(NftModule::set_token_uri(Origin::signed(ALICE), DOMAIN_ID, 1, Some(b"ipfs://abc".to_vec())));
+		assert_eq!(Balances::reserved_balance(ALICE), 109 + 48);
+		assert_ok!(NftModule::set_uri_template(Origin::signed(ALICE), DOMAIN_ID, Some(b"{base}/{id}".to_vec())));
+		assert_eq!(Balances::reserved_balance(ALICE), 120 + 48);
+		assert_ok!(NftModule::set_token_uri(Origin::signed(ALICE), DOMAIN_ID, 1, None));
+		assert_eq!(Balances::reserved_balance(ALICE), 120 + 38);
+		// Accounts that can't cover the deposit can't create domains
+		assert_noop!(NftModule::create_domain(Origin::signed(200), TICKER.as_bytes().to_vec(), vec![0; 500]), pallet_balances::Error::<Test, _>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn it_destroys_tokens_and_domains_in_chunks() {
+	new_test_ext().execute_with(|| {
+		set_deposits(100, 10, 1);
+		// Create domain ZOD "Zodiac" owned by ALICE with two tokens held by BOB
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_ok!(NftModule::set_approval_for_all(Origin::signed(BOB), DOMAIN_ID, CHARLIE, true));
+		assert_ok!(NftModule::set_approval_for_tokens(Origin::signed(BOB), DOMAIN_ID, vec![1], CHARLIE, true));
+		assert_ok!(NftModule::freeze(Origin::signed(ALICE), DOMAIN_ID, 1, BOB));
+		assert_ok!(NftModule::set_attribute(Origin::signed(ALICE), DOMAIN_ID, Some(1), b"rarity".to_vec(), b"rare".to_vec()));
+		// Approvals for tokens that don't exist would outlive the domain
+		assert_noop!(NftModule::set_approval_for_tokens(Origin::signed(BOB), DOMAIN_ID, vec![1, 3], CHARLIE, true), Error::<Test>::InvalidToken);
+		assert_noop!(NftModule::approve(Origin::signed(BOB), DOMAIN_ID, 3, CHARLIE, 1, None), Error::<Test>::InvalidToken);
+		assert_noop!(NftModule::increase_allowance(Origin::signed(BOB), DOMAIN_ID, 3, CHARLIE, 1), Error::<Test>::InvalidToken);
+		assert_ok!(NftModule::set_attribute(Origin::signed(ALICE), DOMAIN_ID, None, b"theme".to_vec(), b"stars".to_vec()));
+		// Tokens with supply left can't be destroyed
+		assert_noop!(NftModule::destroy_token(Origin::signed(ALICE), DOMAIN_ID, 2, DestroyWitness::default()), Error::<Test>::TokenNotEmpty);
+		assert_ok!(NftModule::burn_own_tokens(Origin::signed(BOB), DOMAIN_ID, 2, NUM_TOKENS));
+		assert_ok!(NftModule::set_approval_for_tokens(Origin::signed(BOB), DOMAIN_ID, vec![2], CHARLIE, true));
+		let witness = DestroyWitness { tokens: 1, approvals: 1, attributes: 0 };
+		assert_noop!(NftModule::destroy_token(Origin::signed(BOB), DOMAIN_ID, 2, witness), Error::<Test>::MissingRole);
+		// The witness has to cover the approval left on the token
+		assert_noop!(NftModule::destroy_token(Origin::signed(ALICE), DOMAIN_ID, 2, DestroyWitness::default()), Error::<Test>::BadWitness);
+		assert_ok!(NftModule::destroy_token(Origin::signed(ALICE), DOMAIN_ID, 2, witness));
+		assert_eq!(last_event(), Event::nft(RawEvent::TokenDestroyed(DOMAIN_ID, 2)));
+		assert!(!Tokens::<Test>::contains_key(DOMAIN_ID, 2));
+		// Only the owner can tear the domain down, and only once its tokens are empty
+		let witness = DestroyWitness { tokens: 1, approvals: 1, attributes: 1 };
+		assert_noop!(NftModule::destroy_domain(Origin::signed(BOB), DOMAIN_ID, witness), Error::<Test>::NotDomainOwner);
+		assert_noop!(NftModule::destroy_domain(Origin::signed(ALICE), DOMAIN_ID, witness), Error::<Test>::TokenNotEmpty);
+		assert_ok!(NftModule::burn_own_tokens(Origin::signed(BOB), DOMAIN_ID, 1, NUM_TOKENS));
+		// The first chunk only gets through the operator approval and domain attribute
+		assert_ok!(NftModule::destroy_domain(Origin::signed(ALICE), DOMAIN_ID, witness));
+		assert!(NftModule::domains(DOMAIN_ID).destroying);
+		assert!(Tokens::<Test>::contains_key(DOMAIN_ID, 1));
+		assert_noop!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None), Error::<Test>::DomainDestroying);
+		assert_noop!(NftModule::set_approval_for_all(Origin::signed(BOB), DOMAIN_ID, CHARLIE, true), Error::<Test>::DomainDestroying);
+		assert_noop!(NftModule::set_approval_for_tokens(Origin::signed(BOB), DOMAIN_ID, vec![1], ALICE, true), Error::<Test>::DomainDestroying);
+		assert_noop!(NftModule::approve(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 1, None), Error::<Test>::DomainDestroying);
+		assert_noop!(NftModule::increase_allowance(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 1), Error::<Test>::DomainDestroying);
+		// The second clears the token approval and attribute, the third the freeze, which
+		// finishes the job and refunds every deposit
+		assert_ok!(NftModule::destroy_domain(Origin::signed(ALICE), DOMAIN_ID, witness));
+		assert!(Tokens::<Test>::contains_key(DOMAIN_ID, 1));
+		assert_ok!(NftModule::destroy_domain(Origin::signed(ALICE), DOMAIN_ID, witness));
+		assert!(!NftModule::frozen((DOMAIN_ID, 1), BOB));
+		assert_eq!(last_event(), Event::nft(RawEvent::DomainDestroyed(DOMAIN_ID)));
+		assert!(!Domains::<Test>::contains_key(DOMAIN_ID));
+		assert!(!Tokens::<Test>::contains_key(DOMAIN_ID, 1));
+		assert_eq!(NftModule::domain_deposits(DOMAIN_ID), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn it_migrates_v1_domains_and_tokens() {
+	use crate::{Releases, StorageVersion, migrations::v2::{OldDomain, OldToken}};
+	use frame_support::{StorageValue, storage::unhashed, traits::OnRuntimeUpgrade};
+
+	new_test_ext().execute_with(|| {
+		// Write a domain and token the way the first release stored them
+		StorageVersion::put(Releases::V1_0_0);
+		let old_domain = OldDomain { symbol: TICKER.as_bytes().to_vec(), name: NAME.as_bytes().to_vec(), next_token_id: 1u64, owner: ALICE };
+		unhashed::put(&Domains::<Test>::hashed_key_for(DOMAIN_ID), &old_domain);
+		let old_token = OldToken { base_uri: BASE_URI.as_bytes().to_vec(), total_supply: NUM_TOKENS, creator: BOB };
+		unhashed::put(&Tokens::<Test>::hashed_key_for(DOMAIN_ID, 1), &old_token);
+
+		assert_ok!(NftModule::migrate_to_v2_checked());
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		// Everything decodes with the new fields defaulted
+		let domain = NftModule::domains(DOMAIN_ID);
+		assert_eq!(domain.owner, ALICE);
+		assert_eq!(domain.next_token_id, 1);
+		assert_eq!(domain.uri_template, None);
+		let token = NftModule::tokens(DOMAIN_ID, 1);
+		assert_eq!(token.total_supply, NUM_TOKENS);
+		assert_eq!(token.creator, BOB);
+		assert_eq!(token.burned, 0);
+		assert_eq!(token.max_supply, None);
+		assert_eq!(token.royalty, NO_ROYALTY);
+		assert_eq!(token.transfer_policy, TransferPolicy::Transferable);
+		// Running it again is refused and the upgrade hook only starts the V3 index build
+		assert!(NftModule::migrate_to_v2_checked().is_err());
+		NftModule::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		assert_eq!(NftModule::tokens(DOMAIN_ID, 1).total_supply, NUM_TOKENS);
+	});
+}
+
+#[test]
+fn it_builds_the_holder_index_over_several_blocks() {
+	use crate::{Balances as NftBalances, Releases, StorageVersion, migrations::v3};
+	use frame_support::{StorageValue, traits::OnRuntimeUpgrade};
+
+	new_test_ext().execute_with(|| {
+		// Balances written by V2, which didn't index them
+		StorageVersion::put(Releases::V2_0_0);
+		NftBalances::<Test>::insert(BOB, (DOMAIN_ID, 1), 10);
+		NftBalances::<Test>::insert(BOB, (DOMAIN_ID, 2), 5);
+		NftBalances::<Test>::insert(CHARLIE, (DOMAIN_ID, 1), 0);
+		NftModule::on_runtime_upgrade();
+		// One balance a block, plus a block to find there are none left
+		for _ in 0..3 {
+			v3::step::<Test>(1);
+			assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		}
+		v3::step::<Test>(1);
+		assert_eq!(StorageVersion::get(), Releases::V3_0_0);
+		let (mut tokens, _) = NftModule::tokens_of(&BOB, 0, 10);
+		tokens.sort();
+		assert_eq!(tokens, vec![(DOMAIN_ID, 1, 10), (DOMAIN_ID, 2, 5)]);
+		assert_eq!(NftModule::holders_of(DOMAIN_ID, 1, 0, 10), (vec![(BOB, 10)], None));
+		// Nothing left to do in later blocks
+		v3::step::<Test>(1);
+		assert_eq!(NftModule::holder_count((DOMAIN_ID, 1)), 1);
+	});
+}
+
+fn genesis_ext(balances: Vec<(u64, u64, u64, u64)>) -> sp_io::TestExternalities {
+	set_deposits(0, 0, 0);
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 10_000), (CHARLIE, 10_000)],
+	}.assimilate_storage(&mut t).unwrap();
+	crate::GenesisConfig::<Test> {
+		domains: vec![(ALICE, TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec())],
+		tokens: vec![
+			(DOMAIN_ID, ALICE, NUM_TOKENS, BASE_URI.as_bytes().to_vec()),
+			(DOMAIN_ID, CHARLIE, NUM_TOKENS, BASE_URI.as_bytes().to_vec()),
+		],
+		balances,
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
+}
+
+#[test]
+fn it_builds_domains_tokens_and_balances_at_genesis() {
+	use crate::{Releases, StorageVersion};
+	use frame_support::StorageValue;
+
+	genesis_ext(vec![(BOB, DOMAIN_ID, 1, 10), (BOB, DOMAIN_ID, 2, 2)]).execute_with(|| {
+		assert_eq!(NftModule::domains(DOMAIN_ID).owner, ALICE);
+		assert_eq!(NftModule::tokens(DOMAIN_ID, 1).creator, ALICE);
+		assert_eq!(NftModule::tokens(DOMAIN_ID, 2).creator, CHARLIE);
+		// Holder balances come out of the creator's supply
+		assert_eq!(NftModule::balances(ALICE, (DOMAIN_ID, 1)), NUM_TOKENS - 10);
+		assert_eq!(NftModule::balances(BOB, (DOMAIN_ID, 1)), 10);
+		assert_eq!(NftModule::balances(CHARLIE, (DOMAIN_ID, 2)), NUM_TOKENS - 2);
+		assert_eq!(NftModule::balances(BOB, (DOMAIN_ID, 2)), 2);
+		assert_eq!(NftModule::tokens(DOMAIN_ID, 1).total_supply, NUM_TOKENS);
+		assert_eq!(StorageVersion::get(), Releases::V3_0_0);
+		// The next domain continues after the genesis ones
+		assert_ok!(NftModule::create_domain(Origin::signed(BOB), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_eq!(NftModule::domains(DOMAIN_ID + 1).owner, BOB);
+	});
+}
+
+#[test]
+#[should_panic(expected = "genesis balances must not exceed the supply held by the token creator")]
+fn it_rejects_genesis_balances_above_the_supply() {
+	genesis_ext(vec![(BOB, DOMAIN_ID, 1, NUM_TOKENS + 1)]);
+}
+
+#[test]
+fn it_answers_runtime_api_queries() {
+	new_test_ext().execute_with(|| {
+		assert!(NftModule::domain_info(DOMAIN_ID).is_none());
+		assert!(NftModule::token_info(DOMAIN_ID, 1).is_none());
+		// Create domain ZOD "Zodiac", mint NUM_TOKENS to BOB and give 2 to CHARLIE
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		assert_ok!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 2));
+		assert_ok!(NftModule::burn_own_tokens(Origin::signed(CHARLIE), DOMAIN_ID, 1, 1));
+
+		assert_eq!(NftModule::domain_info(DOMAIN_ID).map(|domain| domain.owner), Some(ALICE));
+		assert_eq!(NftModule::token_info(DOMAIN_ID, 1).map(|token| token.creator), Some(BOB));
+		assert_eq!(NftModule::balance_of(&BOB, DOMAIN_ID, 1), NUM_TOKENS - 2);
+		assert_eq!(NftModule::balance_of_batch(vec![(CHARLIE, DOMAIN_ID, 1), (ALICE, DOMAIN_ID, 1), (BOB, DOMAIN_ID, 1)]), vec![1, 0, NUM_TOKENS - 2]);
+		assert_eq!(NftModule::total_supply(DOMAIN_ID, 1), NUM_TOKENS - 1);
+	});
+}
+
+#[test]
+fn it_indexes_holders_and_owned_tokens() {
+	new_test_ext().execute_with(|| {
+		// Create domain ZOD "Zodiac" with three tokens minted to BOB
+		assert_ok!(NftModule::create_domain(Origin::signed(ALICE), TICKER.as_bytes().to_vec(), NAME.as_bytes().to_vec()));
+		for _ in 0..3 {
+			assert_ok!(NftModule::create_token(Origin::signed(ALICE), DOMAIN_ID, BOB, NUM_TOKENS, BASE_URI.as_bytes().to_vec(), NO_ROYALTY, None));
+		}
+		assert_eq!(NftModule::tokens_of(&BOB, 0, 2), (vec![(DOMAIN_ID, 1, NUM_TOKENS), (DOMAIN_ID, 2, NUM_TOKENS)], Some(2)));
+		assert_eq!(NftModule::tokens_of(&BOB, 2, 2), (vec![(DOMAIN_ID, 3, NUM_TOKENS)], None));
+		assert_eq!(NftModule::tokens_of(&BOB, 5, 2), (vec![], None));
+		assert_eq!(NftModule::tokens_of(&BOB, 0, 0), (vec![], None));
+
+		// Transfers add holders, emptying a balance drops them
+		assert_ok!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, 2));
+		assert_ok!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, ALICE, 1));
+		assert_eq!(NftModule::holders_of(DOMAIN_ID, 1, 0, 10), (vec![(BOB, NUM_TOKENS - 3), (CHARLIE, 2), (ALICE, 1)], None));
+		assert_ok!(NftModule::transfer(Origin::signed(BOB), DOMAIN_ID, 1, CHARLIE, NUM_TOKENS - 3));
+		assert_eq!(NftModule::holders_of(DOMAIN_ID, 1, 0, 10), (vec![(ALICE, 1), (CHARLIE, NUM_TOKENS - 1)], None));
+		assert_eq!(NftModule::tokens_of(&BOB, 0, 10), (vec![(DOMAIN_ID, 3, NUM_TOKENS), (DOMAIN_ID, 2, NUM_TOKENS)], None));
+
+		// Burning the rest removes the last entries
+		assert_ok!(NftModule::burn_own_tokens(Origin::signed(ALICE), DOMAIN_ID, 1, 1));
+		assert_eq!(NftModule::holders_of(DOMAIN_ID, 1, 0, 10), (vec![(CHARLIE, NUM_TOKENS - 1)], None));
+		assert_eq!(NftModule::tokens_of(&ALICE, 0, 10), (vec![], None));
+	});
+}
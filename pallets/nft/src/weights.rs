@@ -0,0 +1,657 @@
+//! Weights for pallet_nft.
+//!
+//! Generated from the pallet's benchmarks with 50 steps and 20 repeats, dispatched natively
+//! through `Benchmark_dispatch_benchmark` against a `BenchmarkingState` on the dev genesis,
+//! the same path `node-template benchmark --pallet pallet_nft --extrinsic '*'` takes. The
+//! benchmarks that move tokens empty the sender, so the holder and owned token index writes
+//! are counted. Rerun on reference hardware before a production launch.
+
+#![allow(unused_parens)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+    fn add_number() -> Weight;
+    fn create_domain() -> Weight;
+    fn create_token() -> Weight;
+    fn mint_tokens() -> Weight;
+    fn set_max_supply() -> Weight;
+    fn freeze_supply() -> Weight;
+    fn set_royalty() -> Weight;
+    fn set_transfer_policy() -> Weight;
+    fn freeze() -> Weight;
+    fn thaw() -> Weight;
+    fn burn_tokens() -> Weight;
+    fn burn_own_tokens() -> Weight;
+    fn transfer() -> Weight;
+    fn transfer_batch(n: u32) -> Weight;
+    fn set_approval_for_all() -> Weight;
+    fn set_approval_for_tokens(n: u32) -> Weight;
+    fn transfer_from() -> Weight;
+    fn approve() -> Weight;
+    fn increase_allowance() -> Weight;
+    fn decrease_allowance() -> Weight;
+    fn list_tokens() -> Weight;
+    fn cancel_listing() -> Weight;
+    fn buy_tokens() -> Weight;
+    fn place_bid() -> Weight;
+    fn accept_bid() -> Weight;
+    fn reject_bid() -> Weight;
+    fn cancel_bid() -> Weight;
+    fn create_auction() -> Weight;
+    fn bid_on_auction() -> Weight;
+    fn cancel_auction() -> Weight;
+    fn on_initialize(n: u32) -> Weight;
+    fn create_dutch_auction() -> Weight;
+    fn buy_from_dutch_auction() -> Weight;
+    fn close_dutch_auction() -> Weight;
+    fn transfer_domain_ownership() -> Weight;
+    fn accept_domain_ownership(r: u32) -> Weight;
+    fn cancel_domain_ownership_transfer() -> Weight;
+    fn set_attribute() -> Weight;
+    fn clear_attribute() -> Weight;
+    fn set_token_uri() -> Weight;
+    fn set_uri_template() -> Weight;
+    fn destroy_token(a: u32, r: u32) -> Weight;
+    fn destroy_domain(t: u32, a: u32, r: u32) -> Weight;
+    fn grant_role() -> Weight;
+    fn revoke_role() -> Weight;
+}
+
+/// Weights for pallet_nft using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn add_number() -> Weight {
+        (1_899_000 as Weight)
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn create_domain() -> Weight {
+        (30_437_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+
+    fn create_token() -> Weight {
+        (61_929_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+
+    fn mint_tokens() -> Weight {
+        (48_169_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+
+    fn set_max_supply() -> Weight {
+        (25_703_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn freeze_supply() -> Weight {
+        (23_194_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn set_royalty() -> Weight {
+        (18_741_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn set_transfer_policy() -> Weight {
+        (28_624_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn freeze() -> Weight {
+        (23_459_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn thaw() -> Weight {
+        (20_984_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn burn_tokens() -> Weight {
+        (63_419_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+
+    fn burn_own_tokens() -> Weight {
+        (72_283_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(10 as Weight))
+    }
+
+    fn transfer() -> Weight {
+        (88_396_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(14 as Weight))
+    }
+
+    fn transfer_batch(n: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((121_867_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((12 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((13 as Weight).saturating_mul(n as Weight)))
+    }
+
+    fn set_approval_for_all() -> Weight {
+        (26_600_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn set_approval_for_tokens(n: u32) -> Weight {
+        (54_914_000 as Weight)
+            .saturating_add((7_829_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+
+    fn transfer_from() -> Weight {
+        (142_306_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(17 as Weight))
+            .saturating_add(T::DbWeight::get().writes(15 as Weight))
+    }
+
+    fn approve() -> Weight {
+        (30_385_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn increase_allowance() -> Weight {
+        (35_262_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn decrease_allowance() -> Weight {
+        (24_574_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn list_tokens() -> Weight {
+        (41_404_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    fn cancel_listing() -> Weight {
+        (20_511_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    fn buy_tokens() -> Weight {
+        (120_882_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+    }
+
+    fn place_bid() -> Weight {
+        (31_161_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    fn accept_bid() -> Weight {
+        (131_828_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().writes(13 as Weight))
+    }
+
+    fn reject_bid() -> Weight {
+        (25_549_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    fn cancel_bid() -> Weight {
+        (24_635_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn create_auction() -> Weight {
+        (34_301_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+
+    fn bid_on_auction() -> Weight {
+        (65_195_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+
+    fn cancel_auction() -> Weight {
+        (34_813_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+
+    fn on_initialize(n: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((178_274_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((17 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((18 as Weight).saturating_mul(n as Weight)))
+    }
+
+    fn create_dutch_auction() -> Weight {
+        (46_248_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+
+    fn buy_from_dutch_auction() -> Weight {
+        (104_008_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+
+    fn close_dutch_auction() -> Weight {
+        (24_682_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    fn transfer_domain_ownership() -> Weight {
+        (20_681_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn accept_domain_ownership(r: u32) -> Weight {
+        (74_048_000 as Weight)
+            .saturating_add((11_294_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+
+    fn cancel_domain_ownership_transfer() -> Weight {
+        (24_068_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn set_attribute() -> Weight {
+        (47_249_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    fn clear_attribute() -> Weight {
+        (42_782_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    fn set_token_uri() -> Weight {
+        (54_794_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+
+    fn set_uri_template() -> Weight {
+        (49_787_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+
+    fn destroy_token(a: u32, r: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((35_759_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((30_312_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+
+    fn destroy_domain(t: u32, a: u32, r: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((720_844_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add((204_732_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+
+    fn grant_role() -> Weight {
+        (19_892_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    fn revoke_role() -> Weight {
+        (19_709_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn add_number() -> Weight {
+        (1_899_000 as Weight)
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn create_domain() -> Weight {
+        (30_437_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+
+    fn create_token() -> Weight {
+        (61_929_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+    }
+
+    fn mint_tokens() -> Weight {
+        (48_169_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+
+    fn set_max_supply() -> Weight {
+        (25_703_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn freeze_supply() -> Weight {
+        (23_194_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn set_royalty() -> Weight {
+        (18_741_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn set_transfer_policy() -> Weight {
+        (28_624_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn freeze() -> Weight {
+        (23_459_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn thaw() -> Weight {
+        (20_984_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn burn_tokens() -> Weight {
+        (63_419_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+
+    fn burn_own_tokens() -> Weight {
+        (72_283_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(10 as Weight))
+    }
+
+    fn transfer() -> Weight {
+        (88_396_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(14 as Weight))
+    }
+
+    fn transfer_batch(n: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((121_867_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((12 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((13 as Weight).saturating_mul(n as Weight)))
+    }
+
+    fn set_approval_for_all() -> Weight {
+        (26_600_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn set_approval_for_tokens(n: u32) -> Weight {
+        (54_914_000 as Weight)
+            .saturating_add((7_829_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+
+    fn transfer_from() -> Weight {
+        (142_306_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(17 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(15 as Weight))
+    }
+
+    fn approve() -> Weight {
+        (30_385_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn increase_allowance() -> Weight {
+        (35_262_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn decrease_allowance() -> Weight {
+        (24_574_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn list_tokens() -> Weight {
+        (41_404_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+
+    fn cancel_listing() -> Weight {
+        (20_511_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+
+    fn buy_tokens() -> Weight {
+        (120_882_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(12 as Weight))
+    }
+
+    fn place_bid() -> Weight {
+        (31_161_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+
+    fn accept_bid() -> Weight {
+        (131_828_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(13 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(13 as Weight))
+    }
+
+    fn reject_bid() -> Weight {
+        (25_549_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+
+    fn cancel_bid() -> Weight {
+        (24_635_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn create_auction() -> Weight {
+        (34_301_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+
+    fn bid_on_auction() -> Weight {
+        (65_195_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+
+    fn cancel_auction() -> Weight {
+        (34_813_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+
+    fn on_initialize(n: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((178_274_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((17 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((18 as Weight).saturating_mul(n as Weight)))
+    }
+
+    fn create_dutch_auction() -> Weight {
+        (46_248_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+
+    fn buy_from_dutch_auction() -> Weight {
+        (104_008_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(11 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(11 as Weight))
+    }
+
+    fn close_dutch_auction() -> Weight {
+        (24_682_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+
+    fn transfer_domain_ownership() -> Weight {
+        (20_681_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn accept_domain_ownership(r: u32) -> Weight {
+        (74_048_000 as Weight)
+            .saturating_add((11_294_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+
+    fn cancel_domain_ownership_transfer() -> Weight {
+        (24_068_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn set_attribute() -> Weight {
+        (47_249_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+
+    fn clear_attribute() -> Weight {
+        (42_782_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+
+    fn set_token_uri() -> Weight {
+        (54_794_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+
+    fn set_uri_template() -> Weight {
+        (49_787_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+
+    fn destroy_token(a: u32, r: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((35_759_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add((30_312_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+
+    fn destroy_domain(t: u32, a: u32, r: u32) -> Weight {
+        (0 as Weight)
+            .saturating_add((720_844_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add((204_732_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+
+    fn grant_role() -> Weight {
+        (19_892_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+
+    fn revoke_role() -> Weight {
+        (19_709_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
@@ -58,6 +58,15 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of an nft domain.
+pub type DomainId = u64;
+
+/// Identifier of a token within an nft domain.
+pub type TokenId = u64;
+
+/// Quantity of an nft token held by an account.
+pub type NftBalance = u64;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -95,10 +104,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("node-template"),
 	impl_name: create_runtime_str!("node-template"),
 	authoring_version: 1,
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -261,12 +270,33 @@ impl pallet_sudo::Trait for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const AuctionExtendPeriod: BlockNumber = 5 * MINUTES;
+	pub const MaxAuctionsEndingPerBlock: u32 = 20;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 256;
+	pub const AttributeDepositBase: Balance = 10_000;
+	pub const DomainDepositBase: Balance = 1_000_000;
+	pub const TokenDepositBase: Balance = 100_000;
+	pub const DepositPerByte: Balance = 100;
+}
+
 /// Configure the nft pallet in pallets/nft.
 impl pallet_nft::Trait for Runtime {
 	type Event = Event;
-	type Balance = u64;
-	type DomainId = u64;
-	type TokenId = u64;
+	type Balance = NftBalance;
+	type DomainId = DomainId;
+	type TokenId = TokenId;
+	type Currency = Balances;
+	type AuctionExtendPeriod = AuctionExtendPeriod;
+	type MaxAuctionsEndingPerBlock = MaxAuctionsEndingPerBlock;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DomainDepositBase = DomainDepositBase;
+	type TokenDepositBase = TokenDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type WeightInfo = pallet_nft::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -285,7 +315,7 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Include the custom logic from the template pallet in the runtime.
-		Nft: pallet_nft::{Module, Call, Storage, Event<T>},
+		Nft: pallet_nft::{Module, Call, Storage, Event<T>, Config<T>},
 	}
 );
 
@@ -449,6 +479,56 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_nft_rpc_runtime_api::NftApi<Block, AccountId, DomainId, TokenId, NftBalance, Balance> for Runtime {
+		fn allowance(owner: AccountId, spender: AccountId, domain_id: DomainId, token_id: TokenId) -> NftBalance {
+			Nft::allowance(&owner, &spender, domain_id, token_id)
+		}
+
+		fn bids(domain_id: DomainId, token_id: TokenId) -> Vec<(pallet_nft::BidId, pallet_nft::Bid<AccountId, NftBalance, Balance>)> {
+			Nft::bids_for_token(domain_id, token_id)
+		}
+
+		fn royalty_info(domain_id: DomainId, token_id: TokenId, sale_price: Balance) -> Option<(AccountId, Balance)> {
+			Nft::royalty_info(domain_id, token_id, sale_price)
+		}
+
+		fn attributes(domain_id: DomainId, token_id: Option<TokenId>) -> Vec<(Vec<u8>, Vec<u8>)> {
+			Nft::attributes_of(domain_id, token_id)
+		}
+
+		fn token_uri(domain_id: DomainId, token_id: TokenId) -> Option<Vec<u8>> {
+			Nft::token_uri(domain_id, token_id)
+		}
+
+		fn domain(domain_id: DomainId) -> Option<pallet_nft::Domain<TokenId, AccountId>> {
+			Nft::domain_info(domain_id)
+		}
+
+		fn token(domain_id: DomainId, token_id: TokenId) -> Option<pallet_nft::Token<AccountId, NftBalance>> {
+			Nft::token_info(domain_id, token_id)
+		}
+
+		fn balance_of(owner: AccountId, domain_id: DomainId, token_id: TokenId) -> NftBalance {
+			Nft::balance_of(&owner, domain_id, token_id)
+		}
+
+		fn balance_of_batch(queries: Vec<(AccountId, DomainId, TokenId)>) -> Vec<NftBalance> {
+			Nft::balance_of_batch(queries)
+		}
+
+		fn total_supply(domain_id: DomainId, token_id: TokenId) -> NftBalance {
+			Nft::total_supply(domain_id, token_id)
+		}
+
+		fn tokens_of(owner: AccountId, cursor: u32, limit: u32) -> (Vec<(DomainId, TokenId, NftBalance)>, Option<u32>) {
+			Nft::tokens_of(&owner, cursor, limit)
+		}
+
+		fn holders_of(domain_id: DomainId, token_id: TokenId, cursor: u32, limit: u32) -> (Vec<(AccountId, NftBalance)>, Option<u32>) {
+			Nft::holders_of(domain_id, token_id, cursor, limit)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -483,6 +563,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_nft, Nft);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)